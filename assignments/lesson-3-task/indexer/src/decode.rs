@@ -0,0 +1,121 @@
+//! Decoding of vault events from transaction log messages.
+//!
+//! Anchor's `emit!` writes each event as a `Program data: <base64>` log line,
+//! where the payload is the 8-byte event discriminator followed by the Borsh
+//! encoded event. Log lines emitted by other programs (e.g. during a CPI) are
+//! skipped by tracking the `invoke` / `success` / `failed` lines.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use on_chain_vault::events::*;
+
+use crate::error::{IndexerError, Result};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+pub enum VaultEvent {
+    InitializeVault(InitializeVaultEvent),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    ToggleLock(ToggleLockEvent),
+    CloseVault(CloseVaultEvent),
+    SetWithdrawLimit(SetWithdrawLimitEvent),
+    ApproveAllowance(ApproveAllowanceEvent),
+    DelegateWithdraw(DelegateWithdrawEvent),
+    RevokeAllowance(RevokeAllowanceEvent),
+}
+
+impl VaultEvent {
+    /// The vault the event refers to
+    pub fn vault(&self) -> Pubkey {
+        match self {
+            VaultEvent::InitializeVault(e) => e.vault,
+            VaultEvent::Deposit(e) => e.vault,
+            VaultEvent::Withdraw(e) => e.vault,
+            VaultEvent::ToggleLock(e) => e.vault,
+            VaultEvent::CloseVault(e) => e.vault,
+            VaultEvent::SetWithdrawLimit(e) => e.vault,
+            VaultEvent::ApproveAllowance(e) => e.vault,
+            VaultEvent::DelegateWithdraw(e) => e.vault,
+            VaultEvent::RevokeAllowance(e) => e.vault,
+        }
+    }
+
+    /// Decodes a raw event payload (discriminator followed by the Borsh body).
+    ///
+    /// Returns `Ok(None)` for payloads whose discriminator is not a vault event.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        if data.len() < 8 {
+            return Err(IndexerError::TruncatedEvent);
+        }
+        let (discriminator, mut body) = data.split_at(8);
+
+        macro_rules! decode_as {
+            ($variant:ident, $event:ty) => {
+                if discriminator == <$event>::DISCRIMINATOR {
+                    let event = <$event>::deserialize(&mut body).map_err(|source| {
+                        IndexerError::Deserialize {
+                            name: stringify!($event),
+                            source,
+                        }
+                    })?;
+                    return Ok(Some(VaultEvent::$variant(event)));
+                }
+            };
+        }
+
+        decode_as!(InitializeVault, InitializeVaultEvent);
+        decode_as!(Deposit, DepositEvent);
+        decode_as!(Withdraw, WithdrawEvent);
+        decode_as!(ToggleLock, ToggleLockEvent);
+        decode_as!(CloseVault, CloseVaultEvent);
+        decode_as!(SetWithdrawLimit, SetWithdrawLimitEvent);
+        decode_as!(ApproveAllowance, ApproveAllowanceEvent);
+        decode_as!(DelegateWithdraw, DelegateWithdrawEvent);
+        decode_as!(RevokeAllowance, RevokeAllowanceEvent);
+
+        Ok(None)
+    }
+}
+
+/// Extracts the vault events emitted directly by `program_id` from a transaction's logs
+pub fn parse_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Result<Vec<VaultEvent>> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<String> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&program_id) {
+                let bytes = STANDARD.decode(data.trim())?;
+                if let Some(event) = VaultEvent::decode(&bytes)? {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            if let (Some(id), Some(status)) = (parts.next(), parts.next()) {
+                // Program output (`log:`, `data:`, `return:`) can read like an exit, e.g. `msg!("success")`
+                if id.parse::<Pubkey>().is_err() {
+                    continue;
+                }
+                match status {
+                    "invoke" => stack.push(id.to_string()),
+                    "success" | "failed:" => {
+                        stack.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,13 @@
+//! Event-sourced indexer for the on-chain vault program.
+//!
+//! Feed it the log messages of confirmed vault transactions and it rebuilds
+//! per-vault balance and lock history into SQLite. Nothing here talks to the
+//! network, so recorded logs are as good a source as an RPC subscription.
+
+pub mod decode;
+pub mod error;
+pub mod store;
+
+pub use decode::{parse_logs, VaultEvent};
+pub use error::{IndexerError, Result};
+pub use store::{BalanceChange, LockChange, VaultRecord, VaultStore};
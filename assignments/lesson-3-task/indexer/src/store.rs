@@ -0,0 +1,274 @@
+//! SQLite backed projection of vault events.
+//!
+//! Balances are rebuilt from event amounts only, so `balance` is the net amount
+//! deposited into a vault and does not include the rent paid at initialization.
+//! Closing a vault sweeps everything and resets the balance to zero.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::decode::{parse_logs, VaultEvent};
+use crate::error::{IndexerError, Result};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS vaults (
+    vault TEXT PRIMARY KEY,
+    vault_authority TEXT NOT NULL,
+    locked INTEGER NOT NULL,
+    balance INTEGER NOT NULL,
+    closed INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS balance_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    vault TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    signature TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    delta INTEGER NOT NULL,
+    balance INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lock_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    vault TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    signature TEXT NOT NULL,
+    locked INTEGER NOT NULL
+);
+";
+
+pub struct VaultRecord {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub locked: bool,
+    pub balance: i64,
+    pub closed: bool,
+}
+
+pub struct BalanceChange {
+    pub slot: u64,
+    pub signature: String,
+    /// The depositor, authority or delegate that moved the lamports
+    pub actor: Pubkey,
+    pub delta: i64,
+    pub balance: i64,
+}
+
+pub struct LockChange {
+    pub slot: u64,
+    pub signature: String,
+    pub locked: bool,
+}
+
+pub struct VaultStore {
+    conn: Connection,
+    program_id: Pubkey,
+}
+
+impl VaultStore {
+    pub fn open(path: impl AsRef<Path>, program_id: Pubkey) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?, program_id)
+    }
+
+    pub fn open_in_memory(program_id: Pubkey) -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?, program_id)
+    }
+
+    fn with_connection(conn: Connection, program_id: Pubkey) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn, program_id })
+    }
+
+    /// Applies the events of one successful transaction.
+    ///
+    /// Transactions are keyed by signature, so replaying the same transaction is
+    /// a no-op. Returns the number of vault events applied.
+    pub fn ingest_transaction<S: AsRef<str>>(
+        &mut self,
+        slot: u64,
+        signature: &str,
+        logs: &[S],
+    ) -> Result<usize> {
+        let events = parse_logs(&self.program_id, logs)?;
+
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot) VALUES (?1, ?2)",
+            params![signature, slot],
+        )?;
+        if inserted == 0 {
+            return Ok(0);
+        }
+
+        for event in &events {
+            apply_event(&tx, slot, signature, event)?;
+        }
+        tx.commit()?;
+
+        Ok(events.len())
+    }
+
+    pub fn vault(&self, vault: &Pubkey) -> Result<Option<VaultRecord>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT vault, vault_authority, locked, balance, closed FROM vaults WHERE vault = ?1",
+                params![vault.to_string()],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, bool>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get::<_, bool>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        row.map(|(vault, vault_authority, locked, balance, closed)| {
+            Ok(VaultRecord {
+                vault: parse_pubkey(&vault)?,
+                vault_authority: parse_pubkey(&vault_authority)?,
+                locked,
+                balance,
+                closed,
+            })
+        })
+        .transpose()
+    }
+
+    pub fn balance_history(&self, vault: &Pubkey) -> Result<Vec<BalanceChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT slot, signature, actor, delta, balance FROM balance_history
+             WHERE vault = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![vault.to_string()], |row| {
+            Ok((
+                row.get::<_, u64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (slot, signature, actor, delta, balance) = row?;
+            Ok(BalanceChange {
+                slot,
+                signature,
+                actor: parse_pubkey(&actor)?,
+                delta,
+                balance,
+            })
+        })
+        .collect()
+    }
+
+    pub fn lock_history(&self, vault: &Pubkey) -> Result<Vec<LockChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT slot, signature, locked FROM lock_history WHERE vault = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![vault.to_string()], |row| {
+            Ok(LockChange {
+                slot: row.get(0)?,
+                signature: row.get(1)?,
+                locked: row.get(2)?,
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+fn apply_event(conn: &Connection, slot: u64, signature: &str, event: &VaultEvent) -> Result<()> {
+    let vault = event.vault().to_string();
+
+    match event {
+        VaultEvent::InitializeVault(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO vaults (vault, vault_authority, locked, balance, closed)
+                 VALUES (?1, ?2, ?3, 0, 0)",
+                params![vault, e.vault_authority.to_string(), e.locked],
+            )?;
+            record_lock(conn, slot, signature, &vault, e.locked)?;
+        }
+        VaultEvent::ToggleLock(e) => {
+            conn.execute(
+                "UPDATE vaults SET locked = ?2 WHERE vault = ?1",
+                params![vault, e.locked],
+            )?;
+            record_lock(conn, slot, signature, &vault, e.locked)?;
+        }
+        VaultEvent::Deposit(e) => {
+            record_balance(conn, slot, signature, &vault, &e.user, e.amount as i64)?;
+        }
+        VaultEvent::Withdraw(e) => {
+            record_balance(conn, slot, signature, &vault, &e.vault_authority, -(e.amount as i64))?;
+        }
+        VaultEvent::DelegateWithdraw(e) => {
+            record_balance(conn, slot, signature, &vault, &e.delegate, -(e.amount as i64))?;
+        }
+        VaultEvent::CloseVault(e) => {
+            let balance = current_balance(conn, &vault)?;
+            record_balance(conn, slot, signature, &vault, &e.vault_authority, -balance)?;
+            conn.execute("UPDATE vaults SET closed = 1 WHERE vault = ?1", params![vault])?;
+        }
+        VaultEvent::SetWithdrawLimit(_)
+        | VaultEvent::ApproveAllowance(_)
+        | VaultEvent::RevokeAllowance(_) => {}
+    }
+
+    Ok(())
+}
+
+fn current_balance(conn: &Connection, vault: &str) -> Result<i64> {
+    Ok(conn
+        .query_row(
+            "SELECT balance FROM vaults WHERE vault = ?1",
+            params![vault],
+            |row| row.get(0),
+        )
+        .optional()?
+        .unwrap_or(0))
+}
+
+fn record_balance(
+    conn: &Connection,
+    slot: u64,
+    signature: &str,
+    vault: &str,
+    actor: &Pubkey,
+    delta: i64,
+) -> Result<()> {
+    let balance = current_balance(conn, vault)? + delta;
+    conn.execute(
+        "UPDATE vaults SET balance = ?2 WHERE vault = ?1",
+        params![vault, balance],
+    )?;
+    conn.execute(
+        "INSERT INTO balance_history (vault, slot, signature, actor, delta, balance)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![vault, slot, signature, actor.to_string(), delta, balance],
+    )?;
+    Ok(())
+}
+
+fn record_lock(conn: &Connection, slot: u64, signature: &str, vault: &str, locked: bool) -> Result<()> {
+    conn.execute(
+        "INSERT INTO lock_history (vault, slot, signature, locked) VALUES (?1, ?2, ?3, ?4)",
+        params![vault, slot, signature, locked],
+    )?;
+    Ok(())
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| IndexerError::InvalidPubkey(value.to_string()))
+}
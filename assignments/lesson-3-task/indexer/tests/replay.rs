@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use serde::Deserialize;
+use vault_indexer::{parse_logs, VaultEvent, VaultStore};
+
+const ALICE: &str = "3x9az88Dkbxa6tkKByxqEn7jBTJCJCD4dVvou49L24ET";
+const BOB: &str = "9jLkNAaW9E47LQMHvjohy2uAAyr1331bAxgJKFRU7wF6";
+const ALICE_VAULT: &str = "FHqrJ9TxVaChS2UzjwLQEXrubNFmRX4uXi6mCFHpUdGg";
+const BOB_VAULT: &str = "Feykt2xDyHuiNvbFM54deMU7HgJrRmoNnTtjH4fJc33S";
+
+#[derive(Deserialize)]
+struct Fixture {
+    program_id: String,
+    transactions: Vec<RecordedTransaction>,
+}
+
+#[derive(Deserialize)]
+struct RecordedTransaction {
+    slot: u64,
+    signature: String,
+    logs: Vec<String>,
+}
+
+fn load_fixture() -> Fixture {
+    serde_json::from_str(include_str!("fixtures/vault_history.json")).unwrap()
+}
+
+fn key(value: &str) -> Pubkey {
+    Pubkey::from_str(value).unwrap()
+}
+
+fn replay(store: &mut VaultStore, fixture: &Fixture) -> usize {
+    fixture
+        .transactions
+        .iter()
+        .map(|tx| store.ingest_transaction(tx.slot, &tx.signature, &tx.logs).unwrap())
+        .sum()
+}
+
+#[test]
+fn decodes_exact_event_payloads() {
+    let fixture = load_fixture();
+    let program_id = key(&fixture.program_id);
+
+    let events = parse_logs(&program_id, &fixture.transactions[2].logs).unwrap();
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        VaultEvent::Deposit(e) => {
+            assert_eq!(e.amount, 1_000_000);
+            assert_eq!(e.user, key(ALICE));
+            assert_eq!(e.vault, key(ALICE_VAULT));
+        }
+        _ => panic!("expected a deposit event"),
+    }
+
+    let events = parse_logs(&program_id, &fixture.transactions[10].logs).unwrap();
+    match &events[0] {
+        VaultEvent::DelegateWithdraw(e) => {
+            assert_eq!(e.amount, 100_000);
+            assert_eq!(e.delegate, key(BOB));
+            assert_eq!(e.remaining_allowance, 100_000);
+        }
+        _ => panic!("expected a delegate withdraw event"),
+    }
+}
+
+#[test]
+fn ignores_program_data_from_other_programs() {
+    let fixture = load_fixture();
+    let program_id = key(&fixture.program_id);
+
+    let events = parse_logs(&program_id, &fixture.transactions[5].logs).unwrap();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn program_logs_that_read_like_an_exit_keep_the_frame() {
+    let fixture = load_fixture();
+    let program_id = key(&fixture.program_id);
+
+    // `msg!("success")` inside the deposit, before its event is emitted
+    let mut logs = fixture.transactions[2].logs.clone();
+    logs.insert(2, "Program log: success".to_string());
+
+    let events = parse_logs(&program_id, &logs).unwrap();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], VaultEvent::Deposit(_)));
+}
+
+#[test]
+fn rebuilds_balance_and_lock_history() {
+    let fixture = load_fixture();
+    let mut store = VaultStore::open_in_memory(key(&fixture.program_id)).unwrap();
+
+    assert_eq!(replay(&mut store, &fixture), 12);
+
+    let vault = store.vault(&key(ALICE_VAULT)).unwrap().unwrap();
+    assert_eq!(vault.vault_authority, key(ALICE));
+    assert!(!vault.locked);
+    assert!(vault.closed);
+    assert_eq!(vault.balance, 0);
+
+    let balances: Vec<(i64, i64)> = store
+        .balance_history(&key(ALICE_VAULT))
+        .unwrap()
+        .iter()
+        .map(|change| (change.delta, change.balance))
+        .collect();
+    assert_eq!(
+        balances,
+        vec![
+            (1_000_000, 1_000_000),
+            (500_000, 1_500_000),
+            (-300_000, 1_200_000),
+            (-100_000, 1_100_000),
+            (-1_100_000, 0),
+        ]
+    );
+
+    let history = store.balance_history(&key(ALICE_VAULT)).unwrap();
+    assert_eq!(history[1].actor, key(BOB));
+    assert_eq!(history[3].actor, key(BOB));
+    assert_eq!(history[3].slot, 107);
+
+    let locks: Vec<bool> = store
+        .lock_history(&key(ALICE_VAULT))
+        .unwrap()
+        .iter()
+        .map(|change| change.locked)
+        .collect();
+    assert_eq!(locks, vec![false, true, false]);
+
+    let bob_vault = store.vault(&key(BOB_VAULT)).unwrap().unwrap();
+    assert!(bob_vault.locked);
+    assert!(!bob_vault.closed);
+    assert!(store.balance_history(&key(BOB_VAULT)).unwrap().is_empty());
+}
+
+#[test]
+fn replaying_a_transaction_is_idempotent() {
+    let fixture = load_fixture();
+    let mut store = VaultStore::open_in_memory(key(&fixture.program_id)).unwrap();
+
+    replay(&mut store, &fixture);
+    assert_eq!(replay(&mut store, &fixture), 0);
+
+    assert_eq!(store.balance_history(&key(ALICE_VAULT)).unwrap().len(), 5);
+    assert_eq!(store.lock_history(&key(ALICE_VAULT)).unwrap().len(), 3);
+}
+
+#[test]
+fn persists_to_a_database_file() {
+    let fixture = load_fixture();
+    let path = std::env::temp_dir().join(format!("vault-indexer-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    {
+        let mut store = VaultStore::open(&path, key(&fixture.program_id)).unwrap();
+        let tx = &fixture.transactions[0];
+        store.ingest_transaction(tx.slot, &tx.signature, &tx.logs).unwrap();
+    }
+
+    let store = VaultStore::open(&path, key(&fixture.program_id)).unwrap();
+    let vault = store.vault(&key(ALICE_VAULT)).unwrap().unwrap();
+    assert_eq!(vault.vault_authority, key(ALICE));
+
+    std::fs::remove_file(&path).unwrap();
+}
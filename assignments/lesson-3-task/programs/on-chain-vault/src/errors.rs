@@ -8,4 +8,16 @@ pub enum VaultError {
     Overflow,
     #[msg("Insufficient balance")]
     InsufficientBalance,
+    #[msg("Withdrawal would leave vault below rent-exempt minimum")]
+    BelowRentExempt,
+    #[msg("Withdrawal exceeds the vault rate limit")]
+    RateLimited,
+    #[msg("Invalid withdrawal limit")]
+    InvalidWithdrawLimit,
+    #[msg("Allowance exceeded")]
+    AllowanceExceeded,
+    #[msg("Allowance expired")]
+    AllowanceExpired,
+    #[msg("Vault has open allowances")]
+    AllowancesOutstanding,
 }
\ No newline at end of file
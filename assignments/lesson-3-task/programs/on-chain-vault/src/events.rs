@@ -26,4 +26,42 @@ pub struct ToggleLockEvent {
     pub vault: Pubkey,
     pub vault_authority: Pubkey,
     pub locked: bool,
+}
+
+#[event]
+pub struct CloseVaultEvent {
+    pub amount: u64,
+    pub vault_authority: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct SetWithdrawLimitEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub withdraw_limit: u64,
+    pub window_seconds: i64,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct ApproveAllowanceEvent {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct DelegateWithdrawEvent {
+    pub amount: u64,
+    pub delegate: Pubkey,
+    pub vault: Pubkey,
+    pub remaining_allowance: u64,
+}
+
+#[event]
+pub struct RevokeAllowanceEvent {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+//-------------------------------------------------------------------------------
+///
+/// Approve Allowance Instruction
+///
+/// Requirements:
+/// - Only the vault authority can grant an allowance
+/// - Create (or overwrite) the allowance PDA for the (vault, delegate) pair
+/// - An optional expiry must lie in the future
+/// - Count newly created allowances on the vault
+/// - Emit an approve allowance event
+///
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use crate::state::{Allowance, Vault};
+use crate::errors::VaultError;
+use crate::events::ApproveAllowanceEvent;
+
+#[derive(Accounts)]
+pub struct ApproveAllowance<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = vault.vault_authority == vault_authority.key(),
+        seeds = [b"vault", vault_authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: The delegate only needs to be a key, it is validated when it signs delegate_withdraw
+    pub delegate: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = vault_authority,
+        space = 8 + Allowance::INIT_SPACE,
+        seeds = [b"allowance", vault.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _approve_allowance(
+    ctx: Context<ApproveAllowance>,
+    amount: u64,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            VaultError::AllowanceExpired
+        );
+    }
+
+    let allowance = &mut ctx.accounts.allowance;
+
+    // `init_if_needed` hands over a zeroed account the first time, overwrites keep their count
+    if allowance.vault == Pubkey::default() {
+        let vault = &mut ctx.accounts.vault;
+        vault.open_allowances = vault
+            .open_allowances
+            .checked_add(1)
+            .ok_or(VaultError::Overflow)?;
+    }
+
+    allowance.vault = ctx.accounts.vault.key();
+    allowance.delegate = ctx.accounts.delegate.key();
+    allowance.amount = amount;
+    allowance.expires_at = expires_at;
+    allowance.bump = ctx.bumps.allowance;
+
+    emit!(ApproveAllowanceEvent {
+        vault: allowance.vault,
+        delegate: allowance.delegate,
+        amount,
+        expires_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//-------------------------------------------------------------------------------
+///
+/// Close Vault Instruction
+///
+/// Requirements:
+/// - Only the vault authority can close the vault
+/// - Verify that the vault is not locked
+/// - Verify that every allowance has been revoked, a vault re-initialized at
+///   the same address would otherwise honour the old allowances
+/// - Verify that the balance above rent fits within the vault rate limit, so
+///   closing can't get around the withdraw limit
+/// - Sweep all remaining lamports (including rent) to the vault authority
+/// - Close the vault account
+/// - Emit a close vault event with the swept amount
+///
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::errors::VaultError;
+use crate::events::CloseVaultEvent;
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        close = vault_authority,
+        constraint = !vault.locked @ VaultError::VaultLocked,
+        constraint = vault.open_allowances == 0 @ VaultError::AllowancesOutstanding,
+        constraint = vault.vault_authority == vault_authority.key(),
+        seeds = [b"vault", vault_authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _close_vault(ctx: Context<CloseVault>) -> Result<()> {
+    // The `close` constraint moves the lamports once the instruction succeeds,
+    // so the amount swept is whatever the vault holds right now
+    let amount = ctx.accounts.vault.to_account_info().lamports();
+
+    // Everything above rent leaves the vault like a withdrawal, so it counts
+    // against the rate limit window
+    let rent_minimum = Rent::get()?.minimum_balance(ctx.accounts.vault.to_account_info().data_len());
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .vault
+        .record_withdrawal(amount.saturating_sub(rent_minimum), now)?;
+
+    emit!(CloseVaultEvent {
+        amount,
+        vault_authority: ctx.accounts.vault_authority.key(),
+        vault: ctx.accounts.vault.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//-------------------------------------------------------------------------------
+///
+/// Delegate Withdraw Instruction
+///
+/// Requirements:
+/// - Only the delegate named in the allowance can withdraw
+/// - Verify that the vault is not locked and the allowance has not expired
+/// - Debit the allowance, rejecting amounts above what remains
+/// - Apply the same balance, rent and rate limit checks as withdraw
+/// - Transfer lamports from vault to the delegate
+/// - Emit a delegate withdraw event identifying the delegate
+///
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use crate::state::{Allowance, Vault};
+use crate::errors::VaultError;
+use crate::events::DelegateWithdrawEvent;
+use crate::instructions::withdraw::transfer_from_vault;
+
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+    #[account(
+        mut,
+        constraint = !vault.locked @ VaultError::VaultLocked,
+        seeds = [b"vault", vault.vault_authority.as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        has_one = vault,
+        has_one = delegate,
+        seeds = [b"allowance", vault.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+
+pub fn _delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+    let allowance = &mut ctx.accounts.allowance;
+
+    if let Some(expires_at) = allowance.expires_at {
+        require!(
+            Clock::get()?.unix_timestamp < expires_at,
+            VaultError::AllowanceExpired
+        );
+    }
+
+    allowance.amount = allowance
+        .amount
+        .checked_sub(amount)
+        .ok_or(VaultError::AllowanceExceeded)?;
+
+    transfer_from_vault(
+        &mut ctx.accounts.vault,
+        &ctx.accounts.delegate.to_account_info(),
+        amount,
+    )?;
+
+    emit!(DelegateWithdrawEvent {
+        amount,
+        delegate: ctx.accounts.delegate.key(),
+        vault: ctx.accounts.vault.key(),
+        remaining_allowance: ctx.accounts.allowance.amount,
+    });
+
+    Ok(())
+}
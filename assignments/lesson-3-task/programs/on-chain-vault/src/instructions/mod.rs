@@ -1,9 +1,19 @@
 mod initialize;
 mod deposit;
-mod withdraw;
+pub(crate) mod withdraw;
 mod toggle_lock;
+mod close_vault;
+mod set_withdraw_limit;
+mod approve_allowance;
+mod delegate_withdraw;
+mod revoke_allowance;
 
 pub use initialize::*;
 pub use deposit::*;
 pub use withdraw::*;
-pub use toggle_lock::*;
\ No newline at end of file
+pub use toggle_lock::*;
+pub use close_vault::*;
+pub use set_withdraw_limit::*;
+pub use approve_allowance::*;
+pub use delegate_withdraw::*;
+pub use revoke_allowance::*;
\ No newline at end of file
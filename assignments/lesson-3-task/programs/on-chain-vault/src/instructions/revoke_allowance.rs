@@ -0,0 +1,52 @@
+//-------------------------------------------------------------------------------
+///
+/// Revoke Allowance Instruction
+///
+/// Requirements:
+/// - Only the vault authority can revoke an allowance
+/// - Close the allowance account and return rent to the vault authority
+/// - Release the allowance from the vault's open allowance count
+/// - Emit a revoke allowance event
+///
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use crate::state::{Allowance, Vault};
+use crate::errors::VaultError;
+use crate::events::RevokeAllowanceEvent;
+
+#[derive(Accounts)]
+pub struct RevokeAllowance<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = vault.vault_authority == vault_authority.key(),
+        seeds = [b"vault", vault_authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        close = vault_authority,
+        has_one = vault,
+        seeds = [b"allowance", vault.key().as_ref(), allowance.delegate.as_ref()],
+        bump = allowance.bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+
+pub fn _revoke_allowance(ctx: Context<RevokeAllowance>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.open_allowances = vault
+        .open_allowances
+        .checked_sub(1)
+        .ok_or(VaultError::Overflow)?;
+
+    emit!(RevokeAllowanceEvent {
+        vault: ctx.accounts.vault.key(),
+        delegate: ctx.accounts.allowance.delegate,
+    });
+
+    Ok(())
+}
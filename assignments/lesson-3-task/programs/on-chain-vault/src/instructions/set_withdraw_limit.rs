@@ -0,0 +1,69 @@
+//-------------------------------------------------------------------------------
+///
+/// Set Withdraw Limit Instruction
+///
+/// Requirements:
+/// - Only the vault authority can change the withdrawal limit
+/// - A limit of 0 removes the rate limit, otherwise the window must be positive
+/// - Tighter limits take effect immediately and cancel any pending change
+/// - Looser limits only take effect after WITHDRAW_LIMIT_INCREASE_DELAY
+/// - Emit a set withdraw limit event with the time the limit becomes active
+///
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use crate::state::{Vault, WITHDRAW_LIMIT_INCREASE_DELAY};
+use crate::errors::VaultError;
+use crate::events::SetWithdrawLimitEvent;
+
+#[derive(Accounts)]
+pub struct SetWithdrawLimit<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = vault.vault_authority == vault_authority.key(),
+        seeds = [b"vault", vault_authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _set_withdraw_limit(
+    ctx: Context<SetWithdrawLimit>,
+    withdraw_limit: u64,
+    window_seconds: i64,
+) -> Result<()> {
+    require!(
+        withdraw_limit == 0 || window_seconds > 0,
+        VaultError::InvalidWithdrawLimit
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let vault = &mut ctx.accounts.vault;
+    vault.apply_pending_withdraw_limit(now);
+
+    let effective_at = if vault.is_looser_withdraw_limit(withdraw_limit, window_seconds) {
+        let activates_at = now
+            .checked_add(WITHDRAW_LIMIT_INCREASE_DELAY)
+            .ok_or(VaultError::Overflow)?;
+        vault.pending_withdraw_limit = withdraw_limit;
+        vault.pending_window_seconds = window_seconds;
+        vault.pending_limit_activates_at = activates_at;
+        activates_at
+    } else {
+        vault.withdraw_limit = withdraw_limit;
+        vault.window_seconds = window_seconds;
+        vault.clear_pending_withdraw_limit();
+        now
+    };
+
+    emit!(SetWithdrawLimitEvent {
+        vault: vault.key(),
+        vault_authority: vault.vault_authority,
+        withdraw_limit,
+        window_seconds,
+        effective_at,
+    });
+
+    Ok(())
+}
@@ -5,6 +5,8 @@
 /// Requirements:
 /// - Verify that the vault is not locked
 /// - Verify that the vault has enough balance to withdraw
+/// - Verify that the vault stays above the rent-exempt minimum
+/// - Verify that the withdrawal fits within the vault rate limit
 /// - Transfer lamports from vault to vault authority
 /// - Emit a withdraw event after successful transfer
 /// 
@@ -31,15 +33,11 @@ pub struct Withdraw<'info> {
 }
 
 pub fn _withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-    // Verify that the vault has enough balance to withdraw
-    let vault_balance = ctx.accounts.vault.to_account_info().lamports();
-    require!(vault_balance >= amount, VaultError::InsufficientBalance);
-
-    // Transfer lamports from vault to vault authority using manual lamport manipulation
-    // For PDAs with data, we need to manually transfer lamports
-    // This is the correct way to transfer SOL from a data-carrying account
-    **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **ctx.accounts.vault_authority.try_borrow_mut_lamports()? += amount;
+    transfer_from_vault(
+        &mut ctx.accounts.vault,
+        &ctx.accounts.vault_authority.to_account_info(),
+        amount,
+    )?;
 
     // Emit a withdraw event after successful transfer
     emit!(WithdrawEvent {
@@ -48,5 +46,32 @@ pub fn _withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         vault: ctx.accounts.vault.key(),
     });
 
+    Ok(())
+}
+
+/// Moves `amount` lamports out of the vault after the balance, rent and rate limit checks
+pub(crate) fn transfer_from_vault<'info>(
+    vault: &mut Account<'info, Vault>,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    // Verify that the vault has enough balance to withdraw
+    let vault_balance = vault.to_account_info().lamports();
+    require!(vault_balance >= amount, VaultError::InsufficientBalance);
+
+    // Verify that the vault stays rent exempt after the withdrawal
+    let rent_minimum = Rent::get()?.minimum_balance(vault.to_account_info().data_len());
+    require!(vault_balance - amount >= rent_minimum, VaultError::BelowRentExempt);
+
+    // Track the withdrawal against the rate limit window
+    let now = Clock::get()?.unix_timestamp;
+    vault.record_withdrawal(amount, now)?;
+
+    // Transfer lamports from vault to recipient using manual lamport manipulation
+    // For PDAs with data, we need to manually transfer lamports
+    // This is the correct way to transfer SOL from a data-carrying account
+    **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? += amount;
+
     Ok(())
 }
\ No newline at end of file
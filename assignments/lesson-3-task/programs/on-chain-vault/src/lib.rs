@@ -25,9 +25,9 @@
 
 use anchor_lang::prelude::*;
 mod instructions;
-mod state;
-mod errors;
-mod events;
+pub mod state;
+pub mod errors;
+pub mod events;
 
 use instructions::*;
 
@@ -52,4 +52,24 @@ pub mod on_chain_vault {
     pub fn toggle_lock(ctx: Context<ToggleLock>) -> Result<()> {
       _toggle_lock(ctx)
     }
+
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+      _close_vault(ctx)
+    }
+
+    pub fn set_withdraw_limit(ctx: Context<SetWithdrawLimit>, withdraw_limit: u64, window_seconds: i64) -> Result<()> {
+      _set_withdraw_limit(ctx, withdraw_limit, window_seconds)
+    }
+
+    pub fn approve_allowance(ctx: Context<ApproveAllowance>, amount: u64, expires_at: Option<i64>) -> Result<()> {
+      _approve_allowance(ctx, amount, expires_at)
+    }
+
+    pub fn delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+      _delegate_withdraw(ctx, amount)
+    }
+
+    pub fn revoke_allowance(ctx: Context<RevokeAllowance>) -> Result<()> {
+      _revoke_allowance(ctx)
+    }
 }
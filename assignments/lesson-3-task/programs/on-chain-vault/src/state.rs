@@ -1,8 +1,83 @@
 use anchor_lang::prelude::*;
+use crate::errors::VaultError;
+
+/// Delay before a looser withdrawal limit takes effect (24 hours)
+pub const WITHDRAW_LIMIT_INCREASE_DELAY: i64 = 24 * 60 * 60;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
     pub vault_authority: Pubkey,
     pub locked: bool,
+    // Maximum lamports withdrawable per window, 0 means unlimited
+    pub withdraw_limit: u64,
+    pub window_seconds: i64,
+    pub window_start: i64,
+    pub window_withdrawn: u64,
+    // Looser limit scheduled by the authority, active from `pending_limit_activates_at`
+    pub pending_withdraw_limit: u64,
+    pub pending_window_seconds: i64,
+    pub pending_limit_activates_at: i64,
+    // Allowances granted and not yet revoked, the vault can't be closed while any remain
+    pub open_allowances: u32,
+}
+
+impl Vault {
+    /// Promotes the pending limit once its activation time has passed
+    pub fn apply_pending_withdraw_limit(&mut self, now: i64) {
+        if self.pending_limit_activates_at != 0 && now >= self.pending_limit_activates_at {
+            self.withdraw_limit = self.pending_withdraw_limit;
+            self.window_seconds = self.pending_window_seconds;
+            self.clear_pending_withdraw_limit();
+        }
+    }
+
+    pub fn clear_pending_withdraw_limit(&mut self) {
+        self.pending_withdraw_limit = 0;
+        self.pending_window_seconds = 0;
+        self.pending_limit_activates_at = 0;
+    }
+
+    /// Returns true if the new limit would allow more lamports out than the current one
+    pub fn is_looser_withdraw_limit(&self, withdraw_limit: u64, window_seconds: i64) -> bool {
+        if self.withdraw_limit == 0 {
+            return false;
+        }
+        withdraw_limit == 0
+            || withdraw_limit > self.withdraw_limit
+            || window_seconds < self.window_seconds
+    }
+
+    /// Tracks `amount` against the current window and rejects it if it exceeds the limit
+    pub fn record_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.apply_pending_withdraw_limit(now);
+
+        if self.withdraw_limit == 0 {
+            return Ok(());
+        }
+
+        if now.saturating_sub(self.window_start) >= self.window_seconds {
+            self.window_start = now;
+            self.window_withdrawn = 0;
+        }
+
+        let withdrawn = self
+            .window_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        require!(withdrawn <= self.withdraw_limit, VaultError::RateLimited);
+        self.window_withdrawn = withdrawn;
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub expires_at: Option<i64>,
+    pub bump: u8,
 }
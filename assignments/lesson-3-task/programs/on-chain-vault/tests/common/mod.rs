@@ -0,0 +1,269 @@
+//! Shared harness for the in-process vault tests.
+//!
+//! The compiled program runs inside LiteSVM, so build it with `anchor build`
+//! first. The tests load `target/deploy/on_chain_vault.so`, or the copy in
+//! `SBF_OUT_DIR` when that is set.
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, AccountDeserialize, Discriminator, InstructionData};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use litesvm::LiteSVM;
+use on_chain_vault::state::Vault;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+fn program_path() -> PathBuf {
+    std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"))
+        .join("on_chain_vault.so")
+}
+
+pub fn vault_pda(vault_authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", vault_authority.as_ref()], &on_chain_vault::ID).0
+}
+
+pub fn allowance_pda(vault: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"allowance", vault.as_ref(), delegate.as_ref()], &on_chain_vault::ID).0
+}
+
+pub fn init_vault_ix(vault_authority: &Pubkey, locked: bool) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::InitializeVault {
+            vault_authority: *vault_authority,
+            vault: vault_pda(vault_authority),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::InitVault { locked }.data(),
+    }
+}
+
+pub fn deposit_ix(user: &Pubkey, vault: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::Deposit {
+            user: *user,
+            vault: *vault,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::Deposit { amount }.data(),
+    }
+}
+
+pub fn withdraw_ix(vault_authority: &Pubkey, vault: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::Withdraw {
+            vault_authority: *vault_authority,
+            vault: *vault,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::Withdraw { amount }.data(),
+    }
+}
+
+pub fn toggle_lock_ix(vault_authority: &Pubkey, vault: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::ToggleLock {
+            vault_authority: *vault_authority,
+            vault: *vault,
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::ToggleLock {}.data(),
+    }
+}
+
+pub fn close_vault_ix(vault_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::CloseVault {
+            vault_authority: *vault_authority,
+            vault: vault_pda(vault_authority),
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::CloseVault {}.data(),
+    }
+}
+
+pub fn set_withdraw_limit_ix(vault_authority: &Pubkey, withdraw_limit: u64, window_seconds: i64) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::SetWithdrawLimit {
+            vault_authority: *vault_authority,
+            vault: vault_pda(vault_authority),
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::SetWithdrawLimit {
+            withdraw_limit,
+            window_seconds,
+        }
+        .data(),
+    }
+}
+
+pub fn approve_allowance_ix(vault_authority: &Pubkey, delegate: &Pubkey, amount: u64) -> Instruction {
+    let vault = vault_pda(vault_authority);
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::ApproveAllowance {
+            vault_authority: *vault_authority,
+            vault,
+            delegate: *delegate,
+            allowance: allowance_pda(&vault, delegate),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::ApproveAllowance {
+            amount,
+            expires_at: None,
+        }
+        .data(),
+    }
+}
+
+pub fn delegate_withdraw_ix(delegate: &Pubkey, vault: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::DelegateWithdraw {
+            delegate: *delegate,
+            vault: *vault,
+            allowance: allowance_pda(vault, delegate),
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::DelegateWithdraw { amount }.data(),
+    }
+}
+
+pub fn revoke_allowance_ix(vault_authority: &Pubkey, delegate: &Pubkey) -> Instruction {
+    let vault = vault_pda(vault_authority);
+    Instruction {
+        program_id: on_chain_vault::ID,
+        accounts: on_chain_vault::accounts::RevokeAllowance {
+            vault_authority: *vault_authority,
+            vault,
+            allowance: allowance_pda(&vault, delegate),
+        }
+        .to_account_metas(None),
+        data: on_chain_vault::instruction::RevokeAllowance {}.data(),
+    }
+}
+
+pub struct TxOutcome {
+    pub result: std::result::Result<(), TransactionError>,
+    pub logs: Vec<String>,
+}
+
+impl TxOutcome {
+    /// Decodes every event of type `E` emitted by the transaction
+    pub fn events<E: AnchorDeserialize + Discriminator>(&self) -> Vec<E> {
+        self.logs
+            .iter()
+            .filter_map(|line| line.strip_prefix("Program data: "))
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .filter(|bytes| bytes.starts_with(E::DISCRIMINATOR))
+            .map(|bytes| E::deserialize(&mut &bytes[E::DISCRIMINATOR.len()..]).unwrap())
+            .collect()
+    }
+
+    /// The custom program error code the transaction failed with, if any
+    pub fn error_code(&self) -> Option<u32> {
+        match &self.result {
+            Err(TransactionError::InstructionError(
+                _,
+                anchor_lang::solana_program::instruction::InstructionError::Custom(code),
+            )) => Some(*code),
+            _ => None,
+        }
+    }
+
+    pub fn assert_ok(&self) {
+        assert!(self.result.is_ok(), "transaction failed: {:?}\n{:#?}", self.result, self.logs);
+    }
+
+    pub fn assert_error(&self, code: impl Into<u32>) {
+        let code = code.into();
+        assert_eq!(
+            self.error_code(),
+            Some(code),
+            "expected error {code}, got {:?}\n{:#?}",
+            self.result,
+            self.logs
+        );
+    }
+}
+
+pub struct VaultTest {
+    pub svm: LiteSVM,
+    pub payer: Keypair,
+}
+
+impl VaultTest {
+    /// Starts a fresh SVM with the vault program loaded and each keypair funded with `lamports`
+    pub fn start(funded: &[&Keypair], lamports: u64) -> Self {
+        let mut svm = LiteSVM::new();
+        let path = program_path();
+        svm.add_program_from_file(on_chain_vault::ID, &path)
+            .unwrap_or_else(|err| panic!("loading {} failed, run `anchor build` first: {err:?}", path.display()));
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+        for keypair in funded {
+            svm.airdrop(&keypair.pubkey(), lamports).unwrap();
+        }
+
+        Self { svm, payer }
+    }
+
+    /// Sends `ix` with the payer covering fees, so signer balances only move by the instruction itself
+    pub fn send(&mut self, ix: Instruction, signers: &[&Keypair]) -> TxOutcome {
+        let mut all_signers: Vec<&Keypair> = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        let outcome = match self.svm.send_transaction(tx) {
+            Ok(meta) => TxOutcome {
+                result: Ok(()),
+                logs: meta.logs,
+            },
+            Err(failed) => TxOutcome {
+                result: Err(failed.err),
+                logs: failed.meta.logs,
+            },
+        };
+        // A fresh blockhash keeps otherwise identical transactions from being deduplicated
+        self.svm.expire_blockhash();
+        outcome
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or_default()
+    }
+
+    pub fn vault(&self, address: &Pubkey) -> Vault {
+        let account = self.svm.get_account(address).expect("vault account should exist");
+        Vault::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn rent_minimum(&self, address: &Pubkey) -> u64 {
+        let account = self.svm.get_account(address).unwrap();
+        self.svm.minimum_balance_for_rent_exemption(account.data.len())
+    }
+}
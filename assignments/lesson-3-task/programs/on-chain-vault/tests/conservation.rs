@@ -0,0 +1,90 @@
+mod common;
+
+use proptest::prelude::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+
+const STARTING_LAMPORTS: u64 = 100 * LAMPORTS_PER_SOL;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit(u64),
+    Withdraw(u64),
+    ToggleLock,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (0..=2 * LAMPORTS_PER_SOL).prop_map(Op::Deposit),
+        4 => (0..=3 * LAMPORTS_PER_SOL).prop_map(Op::Withdraw),
+        1 => Just(Op::ToggleLock),
+    ]
+}
+
+/// Runs `ops` against a fresh vault and checks after every step that no lamports
+/// were created or destroyed, and that the vault matches a simple model of it
+fn run(ops: Vec<Op>) {
+    let authority = Keypair::new();
+    let user = Keypair::new();
+    let mut test = VaultTest::start(&[&authority, &user], STARTING_LAMPORTS);
+    let vault = vault_pda(&authority.pubkey());
+
+    test.send(init_vault_ix(&authority.pubkey(), false), &[&authority])
+        .assert_ok();
+    let rent_minimum = test.rent_minimum(&vault);
+
+    // Fees are paid by the harness payer, so these three accounts form a closed system
+    let total = 2 * STARTING_LAMPORTS;
+    let mut locked = false;
+    let mut deposited = 0u64;
+
+    for op in ops {
+        let (ix, signer, expect_ok) = match op {
+            Op::Deposit(amount) => (
+                deposit_ix(&user.pubkey(), &vault, amount),
+                &user,
+                !locked,
+            ),
+            Op::Withdraw(amount) => (
+                withdraw_ix(&authority.pubkey(), &vault, amount),
+                &authority,
+                !locked && amount <= deposited,
+            ),
+            Op::ToggleLock => (toggle_lock_ix(&authority.pubkey(), &vault), &authority, true),
+        };
+
+        let outcome = test.send(ix, &[signer]);
+        assert_eq!(
+            outcome.result.is_ok(),
+            expect_ok,
+            "{op:?} with locked={locked}, deposited={deposited}: {:?}",
+            outcome.result
+        );
+
+        if expect_ok {
+            match op {
+                Op::Deposit(amount) => deposited += amount,
+                Op::Withdraw(amount) => deposited -= amount,
+                Op::ToggleLock => locked = !locked,
+            }
+        }
+
+        let vault_balance = test.balance(&vault);
+        let user_balance = test.balance(&user.pubkey());
+        let authority_balance = test.balance(&authority.pubkey());
+
+        assert_eq!(vault_balance + user_balance + authority_balance, total);
+        assert_eq!(vault_balance, rent_minimum + deposited);
+        assert_eq!(test.vault(&vault).locked, locked);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn deposits_and_withdrawals_conserve_lamports(ops in prop::collection::vec(op(), 1..16)) {
+        run(ops);
+    }
+}
@@ -0,0 +1,282 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use on_chain_vault::errors::VaultError;
+use on_chain_vault::events::{DepositEvent, InitializeVaultEvent, ToggleLockEvent, WithdrawEvent};
+use solana_sdk::signature::{Keypair, Signer};
+
+use common::*;
+
+fn setup(locked: bool) -> (VaultTest, Keypair, Keypair) {
+    let authority = Keypair::new();
+    let user = Keypair::new();
+    let mut test = VaultTest::start(&[&authority, &user], 10 * LAMPORTS_PER_SOL);
+
+    test.send(init_vault_ix(&authority.pubkey(), locked), &[&authority])
+        .assert_ok();
+
+    (test, authority, user)
+}
+
+#[test]
+fn init_vault_sets_state_and_emits_event() {
+    let authority = Keypair::new();
+    let mut test = VaultTest::start(&[&authority], 10 * LAMPORTS_PER_SOL);
+    let vault = vault_pda(&authority.pubkey());
+
+    let outcome = test.send(init_vault_ix(&authority.pubkey(), true), &[&authority]);
+    outcome.assert_ok();
+
+    let state = test.vault(&vault);
+    assert_eq!(state.vault_authority, authority.pubkey());
+    assert!(state.locked);
+    assert_eq!(state.withdraw_limit, 0);
+
+    let rent_minimum = test.rent_minimum(&vault);
+    assert_eq!(test.balance(&vault), rent_minimum);
+    assert_eq!(
+        test.balance(&authority.pubkey()),
+        10 * LAMPORTS_PER_SOL - rent_minimum
+    );
+
+    let events = outcome.events::<InitializeVaultEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].vault, vault);
+    assert_eq!(events[0].vault_authority, authority.pubkey());
+    assert!(events[0].locked);
+}
+
+#[test]
+fn init_vault_twice_fails() {
+    let (mut test, authority, _) = setup(false);
+
+    let outcome = test.send(init_vault_ix(&authority.pubkey(), true), &[&authority]);
+
+    assert!(outcome.result.is_err());
+    assert!(!test.vault(&vault_pda(&authority.pubkey())).locked);
+}
+
+#[test]
+fn deposit_moves_lamports_and_emits_event() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    let vault_before = test.balance(&vault);
+
+    let outcome = test.send(deposit_ix(&user.pubkey(), &vault, LAMPORTS_PER_SOL), &[&user]);
+    outcome.assert_ok();
+
+    assert_eq!(test.balance(&vault), vault_before + LAMPORTS_PER_SOL);
+    assert_eq!(test.balance(&user.pubkey()), 9 * LAMPORTS_PER_SOL);
+
+    let events = outcome.events::<DepositEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].amount, LAMPORTS_PER_SOL);
+    assert_eq!(events[0].user, user.pubkey());
+    assert_eq!(events[0].vault, vault);
+}
+
+#[test]
+fn deposit_into_locked_vault_fails() {
+    let (mut test, authority, user) = setup(true);
+    let vault = vault_pda(&authority.pubkey());
+    let vault_before = test.balance(&vault);
+
+    let outcome = test.send(deposit_ix(&user.pubkey(), &vault, LAMPORTS_PER_SOL), &[&user]);
+
+    outcome.assert_error(VaultError::VaultLocked);
+    assert!(outcome.events::<DepositEvent>().is_empty());
+    assert_eq!(test.balance(&vault), vault_before);
+    assert_eq!(test.balance(&user.pubkey()), 10 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn deposit_more_than_user_balance_fails() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+
+    let outcome = test.send(deposit_ix(&user.pubkey(), &vault, 11 * LAMPORTS_PER_SOL), &[&user]);
+
+    outcome.assert_error(VaultError::InsufficientBalance);
+    assert_eq!(test.balance(&user.pubkey()), 10 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn withdraw_moves_lamports_and_emits_event() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(deposit_ix(&user.pubkey(), &vault, 2 * LAMPORTS_PER_SOL), &[&user])
+        .assert_ok();
+    let vault_before = test.balance(&vault);
+    let authority_before = test.balance(&authority.pubkey());
+
+    let outcome = test
+        .send(withdraw_ix(&authority.pubkey(), &vault, LAMPORTS_PER_SOL), &[&authority]);
+    outcome.assert_ok();
+
+    assert_eq!(test.balance(&vault), vault_before - LAMPORTS_PER_SOL);
+    assert_eq!(
+        test.balance(&authority.pubkey()),
+        authority_before + LAMPORTS_PER_SOL
+    );
+
+    let events = outcome.events::<WithdrawEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].amount, LAMPORTS_PER_SOL);
+    assert_eq!(events[0].vault_authority, authority.pubkey());
+    assert_eq!(events[0].vault, vault);
+}
+
+#[test]
+fn withdraw_from_locked_vault_fails() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(deposit_ix(&user.pubkey(), &vault, LAMPORTS_PER_SOL), &[&user])
+        .assert_ok();
+    test.send(toggle_lock_ix(&authority.pubkey(), &vault), &[&authority])
+        .assert_ok();
+    let vault_before = test.balance(&vault);
+
+    let outcome = test
+        .send(withdraw_ix(&authority.pubkey(), &vault, LAMPORTS_PER_SOL), &[&authority]);
+
+    outcome.assert_error(VaultError::VaultLocked);
+    assert_eq!(test.balance(&vault), vault_before);
+}
+
+#[test]
+fn withdraw_by_non_authority_fails() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(deposit_ix(&user.pubkey(), &vault, LAMPORTS_PER_SOL), &[&user])
+        .assert_ok();
+    let vault_before = test.balance(&vault);
+
+    // The depositor is not the vault authority, so the vault PDA doesn't match
+    let outcome = test.send(withdraw_ix(&user.pubkey(), &vault, LAMPORTS_PER_SOL), &[&user]);
+
+    outcome.assert_error(ErrorCode::ConstraintSeeds);
+    assert_eq!(test.balance(&vault), vault_before);
+}
+
+#[test]
+fn withdraw_more_than_vault_balance_fails() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(deposit_ix(&user.pubkey(), &vault, LAMPORTS_PER_SOL), &[&user])
+        .assert_ok();
+    let vault_balance = test.balance(&vault);
+
+    let outcome = test
+        .send(withdraw_ix(&authority.pubkey(), &vault, vault_balance + 1), &[&authority]);
+    outcome.assert_error(VaultError::InsufficientBalance);
+
+    // Withdrawing everything would leave the account below rent exemption
+    let outcome = test.send(withdraw_ix(&authority.pubkey(), &vault, vault_balance), &[&authority]);
+    outcome.assert_error(VaultError::BelowRentExempt);
+
+    assert_eq!(test.balance(&vault), vault_balance);
+}
+
+#[test]
+fn toggle_lock_flips_state_and_emits_event() {
+    let (mut test, authority, _) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+
+    for expected in [true, false] {
+        let outcome = test.send(toggle_lock_ix(&authority.pubkey(), &vault), &[&authority]);
+        outcome.assert_ok();
+
+        assert_eq!(test.vault(&vault).locked, expected);
+
+        let events = outcome.events::<ToggleLockEvent>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].vault, vault);
+        assert_eq!(events[0].vault_authority, authority.pubkey());
+        assert_eq!(events[0].locked, expected);
+    }
+}
+
+#[test]
+fn toggle_lock_by_non_authority_fails() {
+    let (mut test, authority, user) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+
+    let outcome = test.send(toggle_lock_ix(&user.pubkey(), &vault), &[&user]);
+
+    outcome.assert_error(ErrorCode::ConstraintRaw);
+    assert!(!test.vault(&vault).locked);
+}
+
+#[test]
+fn close_vault_with_open_allowance_fails() {
+    let (mut test, authority, delegate) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(approve_allowance_ix(&authority.pubkey(), &delegate.pubkey(), LAMPORTS_PER_SOL), &[&authority])
+        .assert_ok();
+    // Overwriting the allowance doesn't open a second one
+    test.send(approve_allowance_ix(&authority.pubkey(), &delegate.pubkey(), 2 * LAMPORTS_PER_SOL), &[&authority])
+        .assert_ok();
+    assert_eq!(test.vault(&vault).open_allowances, 1);
+
+    let outcome = test.send(close_vault_ix(&authority.pubkey()), &[&authority]);
+
+    outcome.assert_error(VaultError::AllowancesOutstanding);
+    assert_eq!(test.vault(&vault).vault_authority, authority.pubkey());
+}
+
+#[test]
+fn allowance_does_not_survive_vault_reinitialization() {
+    let (mut test, authority, delegate) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(approve_allowance_ix(&authority.pubkey(), &delegate.pubkey(), LAMPORTS_PER_SOL), &[&authority])
+        .assert_ok();
+    test.send(revoke_allowance_ix(&authority.pubkey(), &delegate.pubkey()), &[&authority])
+        .assert_ok();
+    assert_eq!(test.vault(&vault).open_allowances, 0);
+
+    test.send(close_vault_ix(&authority.pubkey()), &[&authority])
+        .assert_ok();
+    test.send(init_vault_ix(&authority.pubkey(), false), &[&authority])
+        .assert_ok();
+    test.send(deposit_ix(&authority.pubkey(), &vault, LAMPORTS_PER_SOL), &[&authority])
+        .assert_ok();
+
+    // The same vault address again, but the old allowance is gone with the old vault
+    let outcome = test.send(delegate_withdraw_ix(&delegate.pubkey(), &vault, 1_000), &[&delegate]);
+
+    outcome.assert_error(ErrorCode::AccountNotInitialized);
+    assert_eq!(test.vault(&vault).open_allowances, 0);
+}
+
+#[test]
+fn close_vault_above_the_withdraw_limit_fails() {
+    let (mut test, authority, _) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(deposit_ix(&authority.pubkey(), &vault, 2 * LAMPORTS_PER_SOL), &[&authority])
+        .assert_ok();
+    test.send(set_withdraw_limit_ix(&authority.pubkey(), LAMPORTS_PER_SOL, 3_600), &[&authority])
+        .assert_ok();
+
+    let outcome = test.send(close_vault_ix(&authority.pubkey()), &[&authority]);
+
+    outcome.assert_error(VaultError::RateLimited);
+    assert_eq!(test.balance(&vault), test.rent_minimum(&vault) + 2 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn close_vault_within_the_withdraw_limit_sweeps_the_balance() {
+    let (mut test, authority, _) = setup(false);
+    let vault = vault_pda(&authority.pubkey());
+    test.send(deposit_ix(&authority.pubkey(), &vault, LAMPORTS_PER_SOL), &[&authority])
+        .assert_ok();
+    test.send(set_withdraw_limit_ix(&authority.pubkey(), LAMPORTS_PER_SOL, 3_600), &[&authority])
+        .assert_ok();
+    let authority_before = test.balance(&authority.pubkey());
+    let vault_balance = test.balance(&vault);
+
+    test.send(close_vault_ix(&authority.pubkey()), &[&authority])
+        .assert_ok();
+
+    assert_eq!(test.balance(&vault), 0);
+    assert_eq!(test.balance(&authority.pubkey()), authority_before + vault_balance);
+}
@@ -670,6 +670,292 @@ describe("on-chain-vault", async () => {
       vault: vaultAlicePDA,
     }).signers([alice]).rpc({ commitment: "confirmed" });
   });
+
+  it("Cannot withdraw below the rent-exempt minimum", async () => {
+    const vaultBalance = await provider.connection.getBalance(vaultAnatolyPDA);
+
+    let flag = "This should fail";
+    try {
+      await program.methods.withdraw(new anchor.BN(vaultBalance)).accounts({
+        vaultAuthority: anatoly.publicKey,
+        vault: vaultAnatolyPDA,
+      }).signers([anatoly]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "BelowRentExempt", "Should fail with BelowRentExempt error");
+    }
+    assert.strictEqual(flag, "Failed", "Withdrawing the whole vault balance should fail");
+  });
+
+  it("Cannot close vault without authority", async () => {
+    let flag = "This should fail";
+    try {
+      await program.methods.closeVault().accounts({
+        vaultAuthority: bob.publicKey, // Bob trying to close Anatoly's vault
+        vault: vaultAnatolyPDA,
+      }).signers([bob]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      // This should fail due to seeds constraint
+      assert.isTrue(error.toString().includes("Error"), "Should fail due to seeds constraint - wrong authority");
+    }
+    assert.strictEqual(flag, "Failed", "Closing a vault without proper authority should fail");
+  });
+
+  it("Close Anatoly's vault", async () => {
+    const vaultBalanceBefore = await provider.connection.getBalance(vaultAnatolyPDA);
+
+    let txSig = await program.methods.closeVault().accounts({
+      vaultAuthority: anatoly.publicKey,
+      vault: vaultAnatolyPDA,
+    }).signers([anatoly]).rpc({ commitment: "confirmed" });
+
+    const vaultInfo = await provider.connection.getAccountInfo(vaultAnatolyPDA);
+    assert.isNull(vaultInfo, "Vault account should be closed");
+
+    // Check event was emitted
+    const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+    const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const events = eventParser.parseLogs(tx.meta.logMessages);
+
+    let logsEmitted = false;
+    for (let event of events) {
+      if (event.name === "closeVaultEvent") {
+        logsEmitted = true;
+        assert.strictEqual(event.data.amount.toString(), vaultBalanceBefore.toString(), "Event amount should match the swept vault balance");
+        assert.strictEqual(event.data.vaultAuthority.toString(), anatoly.publicKey.toString(), "Event vault authority should be Anatoly");
+        assert.strictEqual(event.data.vault.toString(), vaultAnatolyPDA.toString(), "Event vault should be Anatoly's vault");
+      }
+    }
+    assert.isTrue(logsEmitted, "CloseVaultEvent should have been emitted");
+  });
+
+  it("Set a withdraw limit on Bob's vault (takes effect immediately)", async () => {
+    const withdrawLimit = 100000;
+    const windowSeconds = 3600;
+
+    let txSig = await program.methods.setWithdrawLimit(new anchor.BN(withdrawLimit), new anchor.BN(windowSeconds)).accounts({
+      vaultAuthority: bob.publicKey,
+      vault: vaultBobPDA,
+    }).signers([bob]).rpc({ commitment: "confirmed" });
+
+    const vaultData = await program.account.vault.fetch(vaultBobPDA);
+    assert.strictEqual(vaultData.withdrawLimit.toString(), withdrawLimit.toString(), "Withdraw limit should be applied immediately");
+    assert.strictEqual(vaultData.windowSeconds.toString(), windowSeconds.toString(), "Window should be applied immediately");
+    assert.strictEqual(vaultData.pendingLimitActivatesAt.toString(), "0", "No limit change should be pending");
+
+    // Check event was emitted
+    const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+    const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const events = eventParser.parseLogs(tx.meta.logMessages);
+
+    let logsEmitted = false;
+    for (let event of events) {
+      if (event.name === "setWithdrawLimitEvent") {
+        logsEmitted = true;
+        assert.strictEqual(event.data.vault.toString(), vaultBobPDA.toString(), "Event vault should be Bob's vault");
+        assert.strictEqual(event.data.withdrawLimit.toString(), withdrawLimit.toString(), "Event limit should match");
+      }
+    }
+    assert.isTrue(logsEmitted, "SetWithdrawLimitEvent should have been emitted");
+  });
+
+  it("Cannot withdraw more than the rate limit allows", async () => {
+    await program.methods.withdraw(new anchor.BN(60000)).accounts({
+      vaultAuthority: bob.publicKey,
+      vault: vaultBobPDA,
+    }).signers([bob]).rpc({ commitment: "confirmed" });
+
+    let flag = "This should fail";
+    try {
+      await program.methods.withdraw(new anchor.BN(60000)).accounts({
+        vaultAuthority: bob.publicKey,
+        vault: vaultBobPDA,
+      }).signers([bob]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "RateLimited", "Should fail with RateLimited error");
+    }
+    assert.strictEqual(flag, "Failed", "Withdrawing over the rate limit should fail");
+  });
+
+  it("Raising the withdraw limit is delayed", async () => {
+    await program.methods.setWithdrawLimit(new anchor.BN(10000000), new anchor.BN(3600)).accounts({
+      vaultAuthority: bob.publicKey,
+      vault: vaultBobPDA,
+    }).signers([bob]).rpc({ commitment: "confirmed" });
+
+    const vaultData = await program.account.vault.fetch(vaultBobPDA);
+    assert.strictEqual(vaultData.withdrawLimit.toString(), "100000", "Current limit should be unchanged");
+    assert.strictEqual(vaultData.pendingWithdrawLimit.toString(), "10000000", "Raised limit should be pending");
+    assert.isTrue(vaultData.pendingLimitActivatesAt.toNumber() > 0, "Pending limit should have an activation time");
+
+    let flag = "This should fail";
+    try {
+      await program.methods.withdraw(new anchor.BN(60000)).accounts({
+        vaultAuthority: bob.publicKey,
+        vault: vaultBobPDA,
+      }).signers([bob]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "RateLimited", "Should still fail with RateLimited error");
+    }
+    assert.strictEqual(flag, "Failed", "Raised limit should not apply before the delay");
+  });
+
+  it("Cannot set withdraw limit without authority", async () => {
+    let flag = "This should fail";
+    try {
+      await program.methods.setWithdrawLimit(new anchor.BN(0), new anchor.BN(0)).accounts({
+        vaultAuthority: alice.publicKey, // Alice trying to lift Bob's limit
+        vault: vaultBobPDA,
+      }).signers([alice]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      // This should fail due to seeds constraint
+      assert.isTrue(error.toString().includes("Error"), "Should fail due to seeds constraint - wrong authority");
+    }
+    assert.strictEqual(flag, "Failed", "Setting withdraw limit without proper authority should fail");
+  });
+
+  const getAllowancePDA = (vault: anchor.web3.PublicKey, delegate: anchor.web3.PublicKey) => {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allowance"), vault.toBuffer(), delegate.toBuffer()],
+      program.programId
+    );
+  };
+
+  it("Alice approves an allowance for Bob", async () => {
+    const [allowancePDA] = getAllowancePDA(vaultAlicePDA, bob.publicKey);
+    const allowanceAmount = 300000;
+
+    let txSig = await program.methods.approveAllowance(new anchor.BN(allowanceAmount), null).accounts({
+      vaultAuthority: alice.publicKey,
+      vault: vaultAlicePDA,
+      delegate: bob.publicKey,
+      allowance: allowancePDA,
+    }).signers([alice]).rpc({ commitment: "confirmed" });
+
+    const allowanceData = await program.account.allowance.fetch(allowancePDA);
+    assert.strictEqual(allowanceData.vault.toString(), vaultAlicePDA.toString(), "Allowance vault should be Alice's vault");
+    assert.strictEqual(allowanceData.delegate.toString(), bob.publicKey.toString(), "Allowance delegate should be Bob");
+    assert.strictEqual(allowanceData.amount.toString(), allowanceAmount.toString(), "Allowance amount should match");
+    assert.isNull(allowanceData.expiresAt, "Allowance should not expire");
+
+    // Check event was emitted
+    const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+    const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const events = eventParser.parseLogs(tx.meta.logMessages);
+
+    let logsEmitted = false;
+    for (let event of events) {
+      if (event.name === "approveAllowanceEvent") {
+        logsEmitted = true;
+        assert.strictEqual(event.data.delegate.toString(), bob.publicKey.toString(), "Event delegate should be Bob");
+        assert.strictEqual(event.data.amount.toString(), allowanceAmount.toString(), "Event amount should match");
+      }
+    }
+    assert.isTrue(logsEmitted, "ApproveAllowanceEvent should have been emitted");
+  });
+
+  it("Bob withdraws from Alice's vault using his allowance", async () => {
+    const [allowancePDA] = getAllowancePDA(vaultAlicePDA, bob.publicKey);
+    const withdrawAmount = 100000;
+    const vaultBalanceBefore = await provider.connection.getBalance(vaultAlicePDA);
+
+    let txSig = await program.methods.delegateWithdraw(new anchor.BN(withdrawAmount)).accounts({
+      delegate: bob.publicKey,
+      vault: vaultAlicePDA,
+      allowance: allowancePDA,
+    }).signers([bob]).rpc({ commitment: "confirmed" });
+
+    const vaultBalanceAfter = await provider.connection.getBalance(vaultAlicePDA);
+    assert.strictEqual(vaultBalanceBefore - vaultBalanceAfter, withdrawAmount, "Vault balance should decrease by the withdrawn amount");
+
+    const allowanceData = await program.account.allowance.fetch(allowancePDA);
+    assert.strictEqual(allowanceData.amount.toString(), "200000", "Allowance should be debited");
+
+    // Check event was emitted
+    const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+    const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const events = eventParser.parseLogs(tx.meta.logMessages);
+
+    let logsEmitted = false;
+    for (let event of events) {
+      if (event.name === "delegateWithdrawEvent") {
+        logsEmitted = true;
+        assert.strictEqual(event.data.amount.toString(), withdrawAmount.toString(), "Event amount should match withdrawal amount");
+        assert.strictEqual(event.data.delegate.toString(), bob.publicKey.toString(), "Event delegate should be Bob");
+        assert.strictEqual(event.data.vault.toString(), vaultAlicePDA.toString(), "Event vault should be Alice's vault");
+        assert.strictEqual(event.data.remainingAllowance.toString(), "200000", "Event should report the remaining allowance");
+      }
+    }
+    assert.isTrue(logsEmitted, "DelegateWithdrawEvent should have been emitted");
+  });
+
+  it("Bob cannot withdraw more than his allowance", async () => {
+    const [allowancePDA] = getAllowancePDA(vaultAlicePDA, bob.publicKey);
+
+    let flag = "This should fail";
+    try {
+      await program.methods.delegateWithdraw(new anchor.BN(200001)).accounts({
+        delegate: bob.publicKey,
+        vault: vaultAlicePDA,
+        allowance: allowancePDA,
+      }).signers([bob]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "AllowanceExceeded", "Should fail with AllowanceExceeded error");
+    }
+    assert.strictEqual(flag, "Failed", "Withdrawing more than the allowance should fail");
+  });
+
+  it("Alice cannot close her vault while Bob's allowance is open", async () => {
+    let flag = "This should fail";
+    try {
+      await program.methods.closeVault().accounts({
+        vaultAuthority: alice.publicKey,
+        vault: vaultAlicePDA,
+      }).signers([alice]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "AllowancesOutstanding", "Should fail with AllowancesOutstanding error");
+    }
+    assert.strictEqual(flag, "Failed", "Closing a vault with open allowances should fail");
+  });
+
+  it("Alice revokes Bob's allowance", async () => {
+    const [allowancePDA] = getAllowancePDA(vaultAlicePDA, bob.publicKey);
+
+    await program.methods.revokeAllowance().accounts({
+      vaultAuthority: alice.publicKey,
+      vault: vaultAlicePDA,
+      allowance: allowancePDA,
+    }).signers([alice]).rpc({ commitment: "confirmed" });
+
+    const allowanceInfo = await provider.connection.getAccountInfo(allowancePDA);
+    assert.isNull(allowanceInfo, "Allowance account should be closed");
+    const vaultData = await program.account.vault.fetch(vaultAlicePDA);
+    assert.strictEqual(vaultData.openAllowances, 0, "Vault should have no open allowances left");
+
+    let flag = "This should fail";
+    try {
+      await program.methods.delegateWithdraw(new anchor.BN(1000)).accounts({
+        delegate: bob.publicKey,
+        vault: vaultAlicePDA,
+        allowance: allowancePDA,
+      }).signers([bob]).rpc({ commitment: "confirmed" });
+    } catch (error) {
+      flag = "Failed";
+      assert.isTrue(error.toString().includes("AccountNotInitialized") || error.toString().includes("Error"), "Should fail with AccountNotInitialized error");
+    }
+    assert.strictEqual(flag, "Failed", "Withdrawing with a revoked allowance should fail");
+  });
 });
 
 async function airdrop(connection: any, address: any, amount = 100 * anchor.web3.LAMPORTS_PER_SOL) {
@@ -0,0 +1,171 @@
+//! Decoding of twitter events from transaction logs and of twitter accounts
+//! from their raw data.
+//!
+//! Anchor's `emit!` writes each event as a `Program data: <base64>` log line,
+//! where the payload is the 8-byte event discriminator followed by the Borsh
+//! encoded event. Data lines written by other programs are skipped by following
+//! the `invoke` / `success` / `failed` lines. Accounts use
+//! the same layout with the account discriminator in front.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use twitter::events::*;
+use twitter::states::{Comment, Reaction, Tweet};
+
+use crate::error::{IndexerError, Result};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Splits off the 8-byte discriminator and deserializes the rest as `T` when
+/// the discriminator matches
+fn decode_as<T: AnchorDeserialize + Discriminator>(
+    name: &'static str,
+    data: &[u8],
+) -> Result<Option<T>> {
+    if data.len() < 8 {
+        return Err(IndexerError::TruncatedEvent);
+    }
+    let (discriminator, mut body) = data.split_at(8);
+    if discriminator != T::DISCRIMINATOR {
+        return Ok(None);
+    }
+    T::deserialize(&mut body)
+        .map(Some)
+        .map_err(|source| IndexerError::Deserialize { name, source })
+}
+
+pub enum TwitterEvent {
+    InitializeTweet(InitializeTweetEvent),
+    AddReaction(AddReactionEvent),
+    RemoveReaction(RemoveReactionEvent),
+    ChangeReaction(ChangeReactionEvent),
+    AddComment(AddCommentEvent),
+    RemoveComment(RemoveCommentEvent),
+    Tip(TipEvent),
+}
+
+impl TwitterEvent {
+    /// The tweet the event refers to
+    pub fn tweet(&self) -> Pubkey {
+        match self {
+            TwitterEvent::InitializeTweet(e) => e.tweet,
+            TwitterEvent::AddReaction(e) => e.tweet,
+            TwitterEvent::RemoveReaction(e) => e.tweet,
+            TwitterEvent::ChangeReaction(e) => e.tweet,
+            TwitterEvent::AddComment(e) => e.tweet,
+            TwitterEvent::RemoveComment(e) => e.tweet,
+            TwitterEvent::Tip(e) => e.tweet,
+        }
+    }
+
+    /// Decodes a raw event payload (discriminator followed by the Borsh body).
+    ///
+    /// Returns `Ok(None)` for payloads whose discriminator is not a twitter event.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        macro_rules! try_decode {
+            ($variant:ident, $event:ty) => {
+                if let Some(event) = decode_as::<$event>(stringify!($event), data)? {
+                    return Ok(Some(TwitterEvent::$variant(event)));
+                }
+            };
+        }
+
+        try_decode!(InitializeTweet, InitializeTweetEvent);
+        try_decode!(AddReaction, AddReactionEvent);
+        try_decode!(RemoveReaction, RemoveReactionEvent);
+        try_decode!(ChangeReaction, ChangeReactionEvent);
+        try_decode!(AddComment, AddCommentEvent);
+        try_decode!(RemoveComment, RemoveCommentEvent);
+        try_decode!(Tip, TipEvent);
+
+        Ok(None)
+    }
+}
+
+pub enum TwitterAccount {
+    Tweet(Box<Tweet>),
+    Comment(Box<Comment>),
+    Reaction(Reaction),
+}
+
+impl TwitterAccount {
+    /// Decodes raw account data as returned by `getAccountInfo` / `getProgramAccounts`.
+    ///
+    /// Returns `Ok(None)` for twitter accounts the feed does not index (profiles,
+    /// follows, ...).
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        if let Some(tweet) = decode_as::<Tweet>("Tweet", data)? {
+            return Ok(Some(TwitterAccount::Tweet(Box::new(tweet))));
+        }
+        if let Some(comment) = decode_as::<Comment>("Comment", data)? {
+            return Ok(Some(TwitterAccount::Comment(Box::new(comment))));
+        }
+        if let Some(reaction) = decode_as::<Reaction>("Reaction", data)? {
+            return Ok(Some(TwitterAccount::Reaction(reaction)));
+        }
+        Ok(None)
+    }
+}
+
+/// The log lines `parse_logs` cares about
+enum LogLine<'a> {
+    /// `Program <id> invoke [<depth>]`
+    Invoke(&'a str),
+    /// `Program <id> success` or `Program <id> failed: <reason>`
+    Exit,
+    /// `Program data: <base64>`
+    Data(&'a str),
+    Other,
+}
+
+impl<'a> LogLine<'a> {
+    fn parse(line: &'a str) -> Self {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            return LogLine::Data(data.trim());
+        }
+        let Some(rest) = line.strip_prefix("Program ") else {
+            return LogLine::Other;
+        };
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        // Program output (`log:`, `return:`) can read like an exit, e.g. `msg!("success")`
+        if words.first().is_none_or(|id| id.parse::<Pubkey>().is_err()) {
+            return LogLine::Other;
+        }
+        match words.as_slice() {
+            [id, "invoke", ..] => LogLine::Invoke(id),
+            [_, "success"] | [_, "failed:", ..] => LogLine::Exit,
+            _ => LogLine::Other,
+        }
+    }
+}
+
+/// Extracts the twitter events from a transaction's logs.
+///
+/// Tweets, reactions and tips all CPI into the system or token program, and
+/// twitter itself may be called from another program, so a data line only
+/// counts when the innermost running invocation is twitter's.
+pub fn parse_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Result<Vec<TwitterEvent>> {
+    let program_id = program_id.to_string();
+    // One entry per running invocation, `true` for twitter's
+    let mut frames: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        match LogLine::parse(line.as_ref()) {
+            LogLine::Invoke(id) => frames.push(id == program_id),
+            LogLine::Exit => {
+                frames.pop();
+            }
+            LogLine::Data(data) if frames.last() == Some(&true) => {
+                if let Some(event) = TwitterEvent::decode(&STANDARD.decode(data)?)? {
+                    events.push(event);
+                }
+            }
+            LogLine::Data(_) | LogLine::Other => {}
+        }
+    }
+
+    Ok(events)
+}
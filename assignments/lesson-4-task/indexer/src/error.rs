@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error("invalid base64 in program data: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("program data is shorter than a discriminator")]
+    TruncatedEvent,
+    #[error("failed to deserialize {name}: {source}")]
+    Deserialize {
+        name: &'static str,
+        source: std::io::Error,
+    },
+    #[error("invalid pubkey stored in index: {0}")]
+    InvalidPubkey(String),
+    #[error("invalid hash stored in index")]
+    InvalidHash,
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
@@ -0,0 +1,15 @@
+//! Feed indexer for the twitter program.
+//!
+//! Feed it account snapshots and the log messages of confirmed twitter
+//! transactions and it maintains an SQLite index of tweets by topic, author and
+//! time, with reaction counts and comment threads. Nothing here talks to the
+//! network, so data recorded from a local test validator works as well as a
+//! live RPC subscription.
+
+pub mod decode;
+pub mod error;
+pub mod store;
+
+pub use decode::{parse_logs, TwitterAccount, TwitterEvent};
+pub use error::{IndexerError, Result};
+pub use store::{CommentNode, CommentRecord, Cursor, FeedStore, Page, TrendingTweet, TweetPage, TweetRecord};
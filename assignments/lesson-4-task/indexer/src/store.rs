@@ -0,0 +1,743 @@
+//! SQLite backed feed of twitter tweets, reactions and comments.
+//!
+//! Two sources feed the same tables. Events from transaction logs carry what
+//! happened and when, which is all the trending window needs. Account
+//! snapshots carry the current content of tweets and comments, which events
+//! only reference by hash. A tweet's reaction counts follow whichever of the
+//! two was applied last, so both should be ingested in slot order.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hash;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use twitter::states::{Comment, Reaction, ReactionType, Tweet, TWEET_REACTION_SEED};
+
+use crate::decode::{parse_logs, TwitterAccount, TwitterEvent};
+use crate::error::{IndexerError, Result};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL,
+    block_time INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS snapshots (
+    account TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tweets (
+    tweet TEXT PRIMARY KEY,
+    author TEXT NOT NULL,
+    topic TEXT NOT NULL,
+    tweet_index INTEGER NOT NULL,
+    content TEXT,
+    content_hash BLOB,
+    created_slot INTEGER NOT NULL,
+    created_at INTEGER,
+    comment_count INTEGER NOT NULL DEFAULT 0,
+    deleted INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS tweets_by_topic ON tweets (topic, created_slot, tweet);
+CREATE INDEX IF NOT EXISTS tweets_by_author ON tweets (author, created_slot, tweet);
+CREATE INDEX IF NOT EXISTS tweets_by_time ON tweets (created_slot, tweet);
+CREATE TABLE IF NOT EXISTS reaction_counts (
+    tweet TEXT NOT NULL,
+    reaction INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (tweet, reaction)
+);
+CREATE TABLE IF NOT EXISTS reactions (
+    reaction_account TEXT PRIMARY KEY,
+    reaction_author TEXT NOT NULL,
+    tweet TEXT NOT NULL,
+    reaction INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS reaction_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tweet TEXT NOT NULL,
+    reaction INTEGER NOT NULL,
+    delta INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS reaction_log_by_time ON reaction_log (block_time);
+CREATE TABLE IF NOT EXISTS comments (
+    comment TEXT PRIMARY KEY,
+    tweet TEXT NOT NULL,
+    comment_author TEXT NOT NULL,
+    id INTEGER NOT NULL,
+    parent_comment TEXT,
+    depth INTEGER NOT NULL,
+    content TEXT,
+    content_hash BLOB,
+    hidden INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER,
+    removed INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS comments_by_tweet ON comments (tweet, id);
+";
+
+const TWEET_COLUMNS: &str = "tweet, author, topic, tweet_index, content, content_hash, created_slot, created_at, comment_count";
+
+const COMMENT_COLUMNS: &str = "comment, tweet, comment_author, id, parent_comment, depth, content, content_hash, hidden, created_at, removed";
+
+pub struct TweetRecord {
+    pub tweet: Pubkey,
+    pub author: Pubkey,
+    pub topic: String,
+    pub index: u64,
+    /// Only known once an account snapshot of the tweet was ingested
+    pub content: Option<String>,
+    pub content_hash: Option<[u8; 32]>,
+    pub created_slot: u64,
+    /// Block time of the creating transaction, unknown for tweets only seen as snapshots
+    pub created_at: Option<i64>,
+    pub reaction_counts: [u64; ReactionType::COUNT],
+    pub comment_count: u64,
+}
+
+impl TweetRecord {
+    pub fn likes(&self) -> u64 {
+        self.reaction_counts[ReactionType::Like.index()]
+    }
+
+    pub fn dislikes(&self) -> u64 {
+        self.reaction_counts[ReactionType::Dislike.index()]
+    }
+
+    /// Position of this tweet in the feed, to continue paging after it
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            created_slot: self.created_slot,
+            tweet: self.tweet,
+        }
+    }
+}
+
+pub struct CommentRecord {
+    pub comment: Pubkey,
+    pub tweet: Pubkey,
+    pub comment_author: Pubkey,
+    pub id: u64,
+    pub parent_comment: Option<Pubkey>,
+    pub depth: u8,
+    /// Only known once an account snapshot of the comment was ingested
+    pub content: Option<String>,
+    pub content_hash: Option<[u8; 32]>,
+    pub hidden: bool,
+    pub created_at: Option<i64>,
+    /// Removed comments stay in the thread so their replies keep a parent
+    pub removed: bool,
+}
+
+pub struct CommentNode {
+    pub comment: CommentRecord,
+    pub replies: Vec<CommentNode>,
+}
+
+pub struct TrendingTweet {
+    pub tweet: TweetRecord,
+    /// Likes minus dislikes added inside the window
+    pub score: i64,
+}
+
+/// Feeds are ordered newest first by creation slot, with the tweet key breaking ties
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cursor {
+    pub created_slot: u64,
+    pub tweet: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Page {
+    pub limit: usize,
+    /// Only return tweets older than this cursor
+    pub before: Option<Cursor>,
+}
+
+impl Page {
+    pub fn first(limit: usize) -> Self {
+        Self { limit, before: None }
+    }
+
+    pub fn before(limit: usize, cursor: Cursor) -> Self {
+        Self {
+            limit,
+            before: Some(cursor),
+        }
+    }
+}
+
+pub struct TweetPage {
+    pub tweets: Vec<TweetRecord>,
+    /// Cursor for the following page, `None` when this was the last one
+    pub next: Option<Cursor>,
+}
+
+pub struct FeedStore {
+    conn: Connection,
+    program_id: Pubkey,
+}
+
+impl FeedStore {
+    pub fn open(path: impl AsRef<Path>, program_id: Pubkey) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?, program_id)
+    }
+
+    pub fn open_in_memory(program_id: Pubkey) -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?, program_id)
+    }
+
+    fn with_connection(conn: Connection, program_id: Pubkey) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn, program_id })
+    }
+
+    /// Applies the events of one successful transaction.
+    ///
+    /// Transactions are keyed by signature, so replaying the same transaction is
+    /// a no-op. Returns the number of twitter events applied.
+    pub fn ingest_transaction<S: AsRef<str>>(
+        &mut self,
+        slot: u64,
+        block_time: i64,
+        signature: &str,
+        logs: &[S],
+    ) -> Result<usize> {
+        let events = parse_logs(&self.program_id, logs)?;
+
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot, block_time) VALUES (?1, ?2, ?3)",
+            params![signature, slot, block_time],
+        )?;
+        if inserted == 0 {
+            return Ok(0);
+        }
+
+        for event in &events {
+            apply_event(&tx, &self.program_id, slot, block_time, event)?;
+        }
+        tx.commit()?;
+
+        Ok(events.len())
+    }
+
+    /// Applies an account snapshot taken at `slot`, empty data meaning the
+    /// account was closed.
+    ///
+    /// Snapshots older than the last one applied for the same account are
+    /// ignored. Returns whether the snapshot changed the index.
+    pub fn ingest_account(&mut self, account: &Pubkey, slot: u64, data: &[u8]) -> Result<bool> {
+        let key = account.to_string();
+
+        let tx = self.conn.transaction()?;
+        let last_slot: Option<u64> = tx
+            .query_row(
+                "SELECT slot FROM snapshots WHERE account = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if last_slot.is_some_and(|last_slot| last_slot > slot) {
+            return Ok(false);
+        }
+
+        let applied = if data.is_empty() {
+            apply_close(&tx, &key)?
+        } else {
+            match TwitterAccount::decode(data)? {
+                Some(TwitterAccount::Tweet(tweet)) => apply_tweet(&tx, &key, slot, &tweet)?,
+                Some(TwitterAccount::Comment(comment)) => apply_comment(&tx, &key, &comment)?,
+                Some(TwitterAccount::Reaction(reaction)) => apply_reaction(&tx, &key, &reaction)?,
+                None => false,
+            }
+        };
+        if !applied {
+            return Ok(false);
+        }
+
+        tx.execute(
+            "INSERT OR REPLACE INTO snapshots (account, slot) VALUES (?1, ?2)",
+            params![key, slot],
+        )?;
+        tx.commit()?;
+
+        Ok(true)
+    }
+
+    pub fn tweet(&self, tweet: &Pubkey) -> Result<Option<TweetRecord>> {
+        let record = self
+            .conn
+            .query_row(
+                &format!("SELECT {TWEET_COLUMNS} FROM tweets WHERE tweet = ?1 AND deleted = 0"),
+                params![tweet.to_string()],
+                TweetRow::from_row,
+            )
+            .optional()?;
+
+        record.map(|row| self.tweet_record(row)).transpose()
+    }
+
+    /// Every live tweet, newest first
+    pub fn recent_tweets(&self, page: &Page) -> Result<TweetPage> {
+        self.tweet_page("?1 IS NULL", None, page)
+    }
+
+    pub fn tweets_by_topic(&self, topic: &str, page: &Page) -> Result<TweetPage> {
+        self.tweet_page("topic = ?1", Some(topic.to_string()), page)
+    }
+
+    pub fn tweets_by_author(&self, author: &Pubkey, page: &Page) -> Result<TweetPage> {
+        self.tweet_page("author = ?1", Some(author.to_string()), page)
+    }
+
+    /// Tweets ranked by likes minus dislikes added with `from <= block_time < to`.
+    ///
+    /// Only tweets that received a like or dislike inside the window are ranked.
+    pub fn trending(&self, from: i64, to: i64, limit: usize) -> Result<Vec<TrendingTweet>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT reaction_log.tweet,
+                    SUM(CASE reaction_log.reaction WHEN ?1 THEN delta WHEN ?2 THEN -delta ELSE 0 END) AS score
+             FROM reaction_log JOIN tweets ON tweets.tweet = reaction_log.tweet
+             WHERE tweets.deleted = 0 AND block_time >= ?3 AND block_time < ?4
+               AND reaction_log.reaction IN (?1, ?2)
+             GROUP BY reaction_log.tweet
+             ORDER BY score DESC, tweets.created_slot DESC, reaction_log.tweet DESC
+             LIMIT ?5",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                ReactionType::Like.index(),
+                ReactionType::Dislike.index(),
+                from,
+                to,
+                limit
+            ],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        )?;
+
+        let mut trending = Vec::new();
+        for row in rows {
+            let (tweet, score) = row?;
+            if let Some(tweet) = self.tweet(&parse_pubkey(&tweet)?)? {
+                trending.push(TrendingTweet { tweet, score });
+            }
+        }
+        Ok(trending)
+    }
+
+    /// Comments on `tweet` as a tree, each level ordered by comment id.
+    ///
+    /// Replies whose parent was never indexed are returned at the top level.
+    pub fn comment_thread(&self, tweet: &Pubkey) -> Result<Vec<CommentNode>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {COMMENT_COLUMNS} FROM comments WHERE tweet = ?1 ORDER BY id"
+        ))?;
+        let comments = stmt
+            .query_map(params![tweet.to_string()], CommentRow::from_row)?
+            .map(|row| row?.into_record())
+            .collect::<Result<Vec<_>>>()?;
+
+        let known: Vec<Pubkey> = comments.iter().map(|comment| comment.comment).collect();
+        let mut children: HashMap<Option<Pubkey>, Vec<CommentRecord>> = HashMap::new();
+        for comment in comments {
+            let parent = comment.parent_comment.filter(|parent| known.contains(parent));
+            children.entry(parent).or_default().push(comment);
+        }
+
+        Ok(build_thread(None, &mut children))
+    }
+
+    fn tweet_page(&self, filter: &str, arg: Option<String>, page: &Page) -> Result<TweetPage> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TWEET_COLUMNS} FROM tweets
+             WHERE deleted = 0 AND {filter}
+               AND (?2 IS NULL OR created_slot < ?2 OR (created_slot = ?2 AND tweet < ?3))
+             ORDER BY created_slot DESC, tweet DESC
+             LIMIT ?4"
+        ))?;
+        let before_slot = page.before.map(|cursor| cursor.created_slot);
+        let before_tweet = page.before.map(|cursor| cursor.tweet.to_string());
+        // One extra row tells whether another page follows
+        let rows = stmt
+            .query_map(
+                params![arg, before_slot, before_tweet, page.limit + 1],
+                TweetRow::from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut tweets = rows
+            .into_iter()
+            .map(|row| self.tweet_record(row))
+            .collect::<Result<Vec<_>>>()?;
+        let next = if tweets.len() > page.limit {
+            tweets.truncate(page.limit);
+            tweets.last().map(TweetRecord::cursor)
+        } else {
+            None
+        };
+
+        Ok(TweetPage { tweets, next })
+    }
+
+    fn tweet_record(&self, row: TweetRow) -> Result<TweetRecord> {
+        let mut reaction_counts = [0; ReactionType::COUNT];
+        let mut stmt = self
+            .conn
+            .prepare("SELECT reaction, count FROM reaction_counts WHERE tweet = ?1")?;
+        let counts = stmt.query_map(params![row.tweet], |row| {
+            Ok((row.get::<_, usize>(0)?, row.get::<_, u64>(1)?))
+        })?;
+        for count in counts {
+            let (reaction, count) = count?;
+            if let Some(slot) = reaction_counts.get_mut(reaction) {
+                *slot = count;
+            }
+        }
+
+        Ok(TweetRecord {
+            tweet: parse_pubkey(&row.tweet)?,
+            author: parse_pubkey(&row.author)?,
+            topic: row.topic,
+            index: row.index,
+            content: row.content,
+            content_hash: row.content_hash.map(parse_hash).transpose()?,
+            created_slot: row.created_slot,
+            created_at: row.created_at,
+            reaction_counts,
+            comment_count: row.comment_count,
+        })
+    }
+}
+
+struct TweetRow {
+    tweet: String,
+    author: String,
+    topic: String,
+    index: u64,
+    content: Option<String>,
+    content_hash: Option<Vec<u8>>,
+    created_slot: u64,
+    created_at: Option<i64>,
+    comment_count: u64,
+}
+
+impl TweetRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            tweet: row.get(0)?,
+            author: row.get(1)?,
+            topic: row.get(2)?,
+            index: row.get(3)?,
+            content: row.get(4)?,
+            content_hash: row.get(5)?,
+            created_slot: row.get(6)?,
+            created_at: row.get(7)?,
+            comment_count: row.get(8)?,
+        })
+    }
+}
+
+struct CommentRow {
+    comment: String,
+    tweet: String,
+    comment_author: String,
+    id: u64,
+    parent_comment: Option<String>,
+    depth: u8,
+    content: Option<String>,
+    content_hash: Option<Vec<u8>>,
+    hidden: bool,
+    created_at: Option<i64>,
+    removed: bool,
+}
+
+impl CommentRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            comment: row.get(0)?,
+            tweet: row.get(1)?,
+            comment_author: row.get(2)?,
+            id: row.get(3)?,
+            parent_comment: row.get(4)?,
+            depth: row.get(5)?,
+            content: row.get(6)?,
+            content_hash: row.get(7)?,
+            hidden: row.get(8)?,
+            created_at: row.get(9)?,
+            removed: row.get(10)?,
+        })
+    }
+
+    fn into_record(self) -> Result<CommentRecord> {
+        Ok(CommentRecord {
+            comment: parse_pubkey(&self.comment)?,
+            tweet: parse_pubkey(&self.tweet)?,
+            comment_author: parse_pubkey(&self.comment_author)?,
+            id: self.id,
+            parent_comment: self.parent_comment.as_deref().map(parse_pubkey).transpose()?,
+            depth: self.depth,
+            content: self.content,
+            content_hash: self.content_hash.map(parse_hash).transpose()?,
+            hidden: self.hidden,
+            created_at: self.created_at,
+            removed: self.removed,
+        })
+    }
+}
+
+fn build_thread(
+    parent: Option<Pubkey>,
+    children: &mut HashMap<Option<Pubkey>, Vec<CommentRecord>>,
+) -> Vec<CommentNode> {
+    children
+        .remove(&parent)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|comment| {
+            let replies = build_thread(Some(comment.comment), children);
+            CommentNode { comment, replies }
+        })
+        .collect()
+}
+
+fn apply_event(
+    conn: &Connection,
+    program_id: &Pubkey,
+    slot: u64,
+    block_time: i64,
+    event: &TwitterEvent,
+) -> Result<()> {
+    let tweet = event.tweet().to_string();
+
+    match event {
+        TwitterEvent::InitializeTweet(e) => {
+            conn.execute(
+                "INSERT INTO tweets (tweet, author, topic, tweet_index, content_hash, created_slot, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (tweet) DO UPDATE SET
+                     created_slot = excluded.created_slot,
+                     created_at = excluded.created_at,
+                     content_hash = COALESCE(tweets.content_hash, excluded.content_hash)",
+                params![
+                    tweet,
+                    e.tweet_author.to_string(),
+                    e.topic,
+                    e.index,
+                    e.content_hash.to_vec(),
+                    slot,
+                    e.created_at
+                ],
+            )?;
+        }
+        TwitterEvent::AddReaction(e) => {
+            let reaction_account = reaction_address(program_id, &e.reaction_author, &e.tweet);
+            conn.execute(
+                "INSERT OR REPLACE INTO reactions (reaction_account, reaction_author, tweet, reaction)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    reaction_account.to_string(),
+                    e.reaction_author.to_string(),
+                    tweet,
+                    e.reaction.index()
+                ],
+            )?;
+            record_reaction(conn, slot, block_time, &tweet, e.reaction, 1)?;
+        }
+        TwitterEvent::RemoveReaction(e) => {
+            let reaction_account = reaction_address(program_id, &e.reaction_author, &e.tweet);
+            conn.execute(
+                "DELETE FROM reactions WHERE reaction_account = ?1",
+                params![reaction_account.to_string()],
+            )?;
+            record_reaction(conn, slot, block_time, &tweet, e.reaction, -1)?;
+        }
+        TwitterEvent::ChangeReaction(e) => {
+            let reaction_account = reaction_address(program_id, &e.reaction_author, &e.tweet);
+            conn.execute(
+                "INSERT OR REPLACE INTO reactions (reaction_account, reaction_author, tweet, reaction)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    reaction_account.to_string(),
+                    e.reaction_author.to_string(),
+                    tweet,
+                    e.new.index()
+                ],
+            )?;
+            // Logged as taking back the old reaction and adding the new one, so
+            // a like turned into a dislike moves the trending score by two
+            record_reaction(conn, slot, block_time, &tweet, e.old, -1)?;
+            record_reaction(conn, slot, block_time, &tweet, e.new, 1)?;
+        }
+        TwitterEvent::AddComment(e) => {
+            let depth = match e.parent_comment {
+                Some(parent) => conn
+                    .query_row(
+                        "SELECT depth + 1 FROM comments WHERE comment = ?1",
+                        params![parent.to_string()],
+                        |row| row.get::<_, u8>(0),
+                    )
+                    .optional()?
+                    .unwrap_or(1),
+                None => 0,
+            };
+            conn.execute(
+                "INSERT INTO comments (comment, tweet, comment_author, id, parent_comment, depth, content_hash, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (comment) DO UPDATE SET
+                     created_at = excluded.created_at,
+                     content_hash = COALESCE(comments.content_hash, excluded.content_hash)",
+                params![
+                    e.comment.to_string(),
+                    tweet,
+                    e.comment_author.to_string(),
+                    e.id,
+                    e.parent_comment.map(|parent| parent.to_string()),
+                    depth,
+                    e.content_hash.to_vec(),
+                    block_time
+                ],
+            )?;
+            conn.execute(
+                "UPDATE tweets SET comment_count = MAX(comment_count, ?2) WHERE tweet = ?1",
+                params![tweet, e.id + 1],
+            )?;
+        }
+        TwitterEvent::RemoveComment(e) => {
+            conn.execute(
+                "UPDATE comments SET removed = 1, content = NULL WHERE comment = ?1",
+                params![e.comment.to_string()],
+            )?;
+        }
+        TwitterEvent::Tip(_) => {}
+    }
+
+    Ok(())
+}
+
+fn record_reaction(
+    conn: &Connection,
+    slot: u64,
+    block_time: i64,
+    tweet: &str,
+    reaction: ReactionType,
+    delta: i64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO reaction_counts (tweet, reaction, count) VALUES (?1, ?2, MAX(?3, 0))
+         ON CONFLICT (tweet, reaction) DO UPDATE SET count = MAX(count + ?3, 0)",
+        params![tweet, reaction.index(), delta],
+    )?;
+    conn.execute(
+        "INSERT INTO reaction_log (tweet, reaction, delta, slot, block_time) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![tweet, reaction.index(), delta, slot, block_time],
+    )?;
+    Ok(())
+}
+
+fn apply_tweet(conn: &Connection, key: &str, slot: u64, tweet: &Tweet) -> Result<bool> {
+    conn.execute(
+        "INSERT INTO tweets (tweet, author, topic, tweet_index, content, content_hash, created_slot, comment_count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT (tweet) DO UPDATE SET
+             content = excluded.content,
+             content_hash = excluded.content_hash,
+             comment_count = excluded.comment_count,
+             deleted = 0",
+        params![
+            key,
+            tweet.tweet_author.to_string(),
+            tweet.topic,
+            tweet.index,
+            tweet.content,
+            tweet.content_hash.to_vec(),
+            slot,
+            tweet.comment_count
+        ],
+    )?;
+
+    conn.execute("DELETE FROM reaction_counts WHERE tweet = ?1", params![key])?;
+    for (reaction, count) in tweet.reaction_counts.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO reaction_counts (tweet, reaction, count) VALUES (?1, ?2, ?3)",
+            params![key, reaction, count],
+        )?;
+    }
+
+    Ok(true)
+}
+
+fn apply_comment(conn: &Connection, key: &str, comment: &Comment) -> Result<bool> {
+    conn.execute(
+        "INSERT INTO comments (comment, tweet, comment_author, id, parent_comment, depth, content, content_hash, hidden)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT (comment) DO UPDATE SET
+             parent_comment = excluded.parent_comment,
+             depth = excluded.depth,
+             content = excluded.content,
+             content_hash = excluded.content_hash,
+             hidden = excluded.hidden,
+             removed = 0",
+        params![
+            key,
+            comment.parent_tweet.to_string(),
+            comment.commentAuthor.to_string(),
+            comment.id,
+            comment.parent_comment.map(|parent| parent.to_string()),
+            comment.depth,
+            comment.content,
+            hash(comment.content.as_bytes()).to_bytes().to_vec(),
+            comment.hidden
+        ],
+    )?;
+    Ok(true)
+}
+
+fn apply_reaction(conn: &Connection, key: &str, reaction: &Reaction) -> Result<bool> {
+    conn.execute(
+        "INSERT OR REPLACE INTO reactions (reaction_account, reaction_author, tweet, reaction)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            key,
+            reaction.reactionAuthor.to_string(),
+            reaction.parent_tweet.to_string(),
+            reaction.reaction.index()
+        ],
+    )?;
+    Ok(true)
+}
+
+/// Marks whichever indexed account lives at `key` as gone
+fn apply_close(conn: &Connection, key: &str) -> Result<bool> {
+    let changed = conn.execute("UPDATE tweets SET deleted = 1 WHERE tweet = ?1", params![key])?
+        + conn.execute(
+            "UPDATE comments SET removed = 1, content = NULL WHERE comment = ?1",
+            params![key],
+        )?
+        + conn.execute("DELETE FROM reactions WHERE reaction_account = ?1", params![key])?;
+    Ok(changed > 0)
+}
+
+fn reaction_address(program_id: &Pubkey, reaction_author: &Pubkey, tweet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            TWEET_REACTION_SEED.as_bytes(),
+            reaction_author.as_ref(),
+            tweet.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| IndexerError::InvalidPubkey(value.to_string()))
+}
+
+fn parse_hash(value: Vec<u8>) -> Result<[u8; 32]> {
+    value.try_into().map_err(|_| IndexerError::InvalidHash)
+}
@@ -0,0 +1,372 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hash;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+use twitter::states::ReactionType;
+use twitter_indexer::{parse_logs, CommentNode, FeedStore, Page};
+
+const ALICE: &str = "3x9az88Dkbxa6tkKByxqEn7jBTJCJCD4dVvou49L24ET";
+const BOB: &str = "9jLkNAaW9E47LQMHvjohy2uAAyr1331bAxgJKFRU7wF6";
+const ALICE_TWEET: &str = "7G7kyrfhb7Dbmd1DfyFf6sqKzBPJ4KjWgFDNaaTHCRT";
+const ALICE_DELETED_TWEET: &str = "4Nh8cLvb5TZHg3mEk8Z3eouP42SiuWuPLepjk639VGHw";
+const BOB_TWEET: &str = "5nannPP9m28a5rSQhU8zTWcytiRpnwpfQgxPbYP2T5L3";
+const BOB_RUST_TWEET: &str = "CTL8DfDXYsb4vi22ZPgpzzj8NQsY7xnxL4FwNPdTiVDi";
+const BOT_PROGRAM: &str = "Hv6gxf3xRWb21JrAQyRFH6nW38r279w3uEmLA9QhTBAq";
+const COMMENTS: [&str; 4] = [
+    "5jKtt5u7FmrisJF2DiCtY1f9S46n3VYJFtnb1jvziYET",
+    "2uk3aKF57AtKyRMKDcg7YJsLQBBvigVTvKWjxe6MPvNX",
+    "2Sy7ZtgopBx5EFG3fEgWupVXDoRs6YwK34BaUVadc3oL",
+    "FuuXiJTvUVQxipyv7BVM3htxuzC5yfqZpH5QPWYsQips",
+];
+
+#[derive(Deserialize)]
+struct Fixture {
+    program_id: String,
+    transactions: Vec<RecordedTransaction>,
+    accounts: Vec<RecordedAccount>,
+}
+
+#[derive(Clone, Deserialize)]
+struct RecordedTransaction {
+    slot: u64,
+    block_time: i64,
+    signature: String,
+    logs: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RecordedAccount {
+    pubkey: String,
+    slot: u64,
+    data: String,
+}
+
+fn load_fixture() -> Fixture {
+    serde_json::from_str(include_str!("fixtures/feed_history.json")).unwrap()
+}
+
+fn key(value: &str) -> Pubkey {
+    Pubkey::from_str(value).unwrap()
+}
+
+fn replay_transactions(store: &mut FeedStore, fixture: &Fixture) -> usize {
+    fixture
+        .transactions
+        .iter()
+        .map(|tx| {
+            store
+                .ingest_transaction(tx.slot, tx.block_time, &tx.signature, &tx.logs)
+                .unwrap()
+        })
+        .sum()
+}
+
+fn replay_accounts(store: &mut FeedStore, fixture: &Fixture) -> usize {
+    fixture
+        .accounts
+        .iter()
+        .filter(|account| {
+            let data = STANDARD.decode(&account.data).unwrap();
+            store
+                .ingest_account(&key(&account.pubkey), account.slot, &data)
+                .unwrap()
+        })
+        .count()
+}
+
+fn replayed_store(fixture: &Fixture) -> FeedStore {
+    let mut store = FeedStore::open_in_memory(key(&fixture.program_id)).unwrap();
+    replay_transactions(&mut store, fixture);
+    replay_accounts(&mut store, fixture);
+    store
+}
+
+fn tweet_keys(page: &twitter_indexer::TweetPage) -> Vec<Pubkey> {
+    page.tweets.iter().map(|tweet| tweet.tweet).collect()
+}
+
+#[test]
+fn indexes_tweets_and_replies_from_events_alone() {
+    let fixture = load_fixture();
+    let mut store = FeedStore::open_in_memory(key(&fixture.program_id)).unwrap();
+    replay_transactions(&mut store, &fixture);
+
+    let tweet = store.tweet(&key(BOB_RUST_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.author, key(BOB));
+    assert_eq!(tweet.topic, "rust");
+    assert_eq!(tweet.index, 1);
+    assert_eq!(tweet.content, None);
+    assert_eq!(
+        tweet.content_hash,
+        Some(hash(b"borrow checker appreciation post").to_bytes())
+    );
+    assert_eq!(tweet.created_slot, 102);
+    assert_eq!(tweet.created_at, Some(1120));
+
+    // Reply depth is derived from the parent's row before any comment snapshot
+    let thread = store.comment_thread(&key(ALICE_TWEET)).unwrap();
+    let reply = &thread[0].replies[0].comment;
+    assert_eq!(reply.comment, key(COMMENTS[1]));
+    assert_eq!(reply.comment_author, key(ALICE));
+    assert_eq!(reply.parent_comment, Some(key(COMMENTS[0])));
+    assert_eq!(reply.depth, 1);
+    assert_eq!(reply.content, None);
+}
+
+#[test]
+fn counts_reactions_only_from_twitter_frames() {
+    let fixture = load_fixture();
+    let mut store = FeedStore::open_in_memory(key(&fixture.program_id)).unwrap();
+    let ingest = |store: &mut FeedStore, tx: &RecordedTransaction, logs: &[String]| {
+        store
+            .ingest_transaction(tx.slot, tx.block_time, &tx.signature, logs)
+            .unwrap()
+    };
+
+    let created = &fixture.transactions[2];
+    assert_eq!(ingest(&mut store, created, &created.logs), 1);
+
+    // Another program logs data from inside twitter's like instruction
+    let liked = &fixture.transactions[7];
+    assert_eq!(ingest(&mut store, liked, &liked.logs), 1);
+    assert_eq!(store.tweet(&key(BOB_RUST_TWEET)).unwrap().unwrap().likes(), 1);
+
+    // A bot program calling twitter's like instruction through a CPI
+    let mut via_cpi = fixture.transactions[16].clone();
+    via_cpi.signature = "via-cpi".to_string();
+    via_cpi.logs = std::iter::once(format!("Program {BOT_PROGRAM} invoke [1]"))
+        .chain(via_cpi.logs.iter().map(|line| line.replace("invoke [1]", "invoke [2]")))
+        .chain(std::iter::once(format!("Program {BOT_PROGRAM} success")))
+        .collect();
+    assert_eq!(ingest(&mut store, &via_cpi, &via_cpi.logs), 1);
+    let tweet = store.tweet(&key(BOB_RUST_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.reaction_counts[ReactionType::Love.index()], 1);
+
+    // Without a twitter invocation no data line is trusted
+    let outside: Vec<String> = liked.logs[4..7].to_vec();
+    let events = parse_logs(&key(&fixture.program_id), &outside).unwrap();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn program_logs_that_read_like_an_exit_keep_the_frame() {
+    let fixture = load_fixture();
+
+    // `msg!("success")` inside the tweet's creation, before its event is emitted
+    let mut logs = fixture.transactions[2].logs.clone();
+    logs.insert(2, "Program log: success".to_string());
+
+    let events = parse_logs(&key(&fixture.program_id), &logs).unwrap();
+    assert_eq!(events.len(), 1);
+}
+
+#[test]
+fn merges_events_and_account_snapshots() {
+    let fixture = load_fixture();
+    let mut store = FeedStore::open_in_memory(key(&fixture.program_id)).unwrap();
+
+    assert_eq!(replay_transactions(&mut store, &fixture), 18);
+
+    // Events alone only know the content hash
+    let tweet = store.tweet(&key(ALICE_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.content, None);
+    assert_eq!(tweet.content_hash, Some(hash(b"gm solana").to_bytes()));
+    assert_eq!(tweet.likes(), 2);
+
+    // The stale snapshot at slot 90 is skipped
+    assert_eq!(replay_accounts(&mut store, &fixture), 6);
+
+    let tweet = store.tweet(&key(ALICE_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.author, key(ALICE));
+    assert_eq!(tweet.topic, "solana");
+    assert_eq!(tweet.index, 0);
+    assert_eq!(tweet.content.as_deref(), Some("gm solana"));
+    assert_eq!(tweet.created_slot, 100);
+    assert_eq!(tweet.created_at, Some(1000));
+    assert_eq!(tweet.likes(), 2);
+    assert_eq!(tweet.dislikes(), 0);
+    assert_eq!(tweet.comment_count, 4);
+
+    let tweet = store.tweet(&key(BOB_RUST_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.likes(), 1);
+    assert_eq!(tweet.reaction_counts[ReactionType::Love.index()], 1);
+
+    // Only seen through events
+    let tweet = store.tweet(&key(BOB_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.content, None);
+    assert_eq!(tweet.likes(), 1);
+    assert_eq!(tweet.dislikes(), 0);
+
+    // Closed by the empty snapshot
+    assert!(store.tweet(&key(ALICE_DELETED_TWEET)).unwrap().is_none());
+}
+
+#[test]
+fn pages_through_feeds_newest_first() {
+    let fixture = load_fixture();
+    let store = replayed_store(&fixture);
+
+    let first = store.recent_tweets(&Page::first(2)).unwrap();
+    assert_eq!(tweet_keys(&first), vec![key(BOB_RUST_TWEET), key(BOB_TWEET)]);
+    let cursor = first.next.unwrap();
+    assert_eq!(cursor.tweet, key(BOB_TWEET));
+
+    let second = store.recent_tweets(&Page::before(2, cursor)).unwrap();
+    assert_eq!(tweet_keys(&second), vec![key(ALICE_TWEET)]);
+    assert!(second.next.is_none());
+
+    let solana = store.tweets_by_topic("solana", &Page::first(10)).unwrap();
+    assert_eq!(tweet_keys(&solana), vec![key(BOB_TWEET), key(ALICE_TWEET)]);
+    assert!(solana.next.is_none());
+
+    // The deleted tweet no longer shows up under its topic or author
+    let rust = store.tweets_by_topic("rust", &Page::first(10)).unwrap();
+    assert_eq!(tweet_keys(&rust), vec![key(BOB_RUST_TWEET)]);
+
+    let bob = store.tweets_by_author(&key(BOB), &Page::first(1)).unwrap();
+    assert_eq!(tweet_keys(&bob), vec![key(BOB_RUST_TWEET)]);
+    let bob = store
+        .tweets_by_author(&key(BOB), &Page::before(1, bob.next.unwrap()))
+        .unwrap();
+    assert_eq!(tweet_keys(&bob), vec![key(BOB_TWEET)]);
+    assert!(bob.next.is_none());
+}
+
+#[test]
+fn ranks_trending_tweets_inside_a_window() {
+    let fixture = load_fixture();
+    let store = replayed_store(&fixture);
+
+    let trending: Vec<(Pubkey, i64)> = store
+        .trending(1150, 1500, 10)
+        .unwrap()
+        .iter()
+        .map(|trending| (trending.tweet.tweet, trending.score))
+        .collect();
+    assert_eq!(
+        trending,
+        vec![
+            (key(ALICE_TWEET), 2),
+            (key(BOB_RUST_TWEET), 1),
+            (key(BOB_TWEET), -1),
+        ]
+    );
+
+    // A like that was taken back nets out, other reaction types don't count
+    let trending: Vec<(Pubkey, i64)> = store
+        .trending(1500, 2600, 10)
+        .unwrap()
+        .iter()
+        .map(|trending| (trending.tweet.tweet, trending.score))
+        .collect();
+    assert_eq!(trending, vec![(key(BOB_RUST_TWEET), 0)]);
+
+    assert_eq!(store.trending(1150, 1500, 1).unwrap().len(), 1);
+}
+
+#[test]
+fn builds_comment_threads() {
+    let fixture = load_fixture();
+    let store = replayed_store(&fixture);
+
+    let thread = store.comment_thread(&key(ALICE_TWEET)).unwrap();
+    let roots: Vec<Pubkey> = thread.iter().map(|node| node.comment.comment).collect();
+    assert_eq!(roots, vec![key(COMMENTS[0]), key(COMMENTS[2])]);
+
+    let first: &CommentNode = &thread[0];
+    assert_eq!(first.comment.content.as_deref(), Some("wagmi"));
+    assert_eq!(first.comment.depth, 0);
+    assert_eq!(first.replies.len(), 1);
+
+    let reply = &first.replies[0];
+    assert_eq!(reply.comment.comment, key(COMMENTS[1]));
+    assert_eq!(reply.comment.depth, 1);
+    assert!(reply.comment.hidden);
+
+    let nested = &reply.replies[0];
+    assert_eq!(nested.comment.comment, key(COMMENTS[3]));
+    assert_eq!(nested.comment.depth, 2);
+    assert_eq!(nested.comment.content, None);
+    assert_eq!(nested.comment.content_hash, Some(hash(b"be nice").to_bytes()));
+    assert_eq!(nested.comment.created_at, Some(2380));
+
+    let removed = &thread[1];
+    assert!(removed.comment.removed);
+    assert!(removed.replies.is_empty());
+
+    assert!(store.comment_thread(&key(BOB_TWEET)).unwrap().is_empty());
+}
+
+#[test]
+fn changed_reactions_move_between_types() {
+    let fixture = load_fixture();
+    let store = replayed_store(&fixture);
+
+    // Carol's dislike of Bob's tweet becomes a like
+    let tweet = store.tweet(&key(BOB_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.likes(), 1);
+    assert_eq!(tweet.dislikes(), 0);
+    assert_eq!(tweet.reaction_counts.iter().sum::<u64>(), 1);
+
+    // Dropping the dislike and adding the like both raise the score
+    let trending = store.trending(2600, 2700, 10).unwrap();
+    assert_eq!(trending.len(), 1);
+    assert_eq!(trending[0].tweet.tweet, key(BOB_TWEET));
+    assert_eq!(trending[0].score, 2);
+
+    // The dislike still counts in the window it was cast in
+    let trending = store.trending(1300, 1350, 10).unwrap();
+    assert_eq!(trending[0].score, -1);
+}
+
+#[test]
+fn redelivered_transactions_do_not_double_count_reactions() {
+    let fixture = load_fixture();
+    let mut store = FeedStore::open_in_memory(key(&fixture.program_id)).unwrap();
+
+    replay_transactions(&mut store, &fixture);
+    assert_eq!(replay_transactions(&mut store, &fixture), 0);
+
+    let tweet = store.tweet(&key(ALICE_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.likes(), 2);
+    assert_eq!(tweet.comment_count, 4);
+    assert_eq!(store.trending(1150, 1500, 10).unwrap()[0].score, 2);
+    assert_eq!(store.trending(2600, 2700, 10).unwrap()[0].score, 2);
+}
+
+#[test]
+fn reopened_feed_keeps_tweets_and_seen_signatures() {
+    let fixture = load_fixture();
+    let path = std::env::temp_dir().join(format!("twitter-feed-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let created = &fixture.transactions[0];
+    let liked = &fixture.transactions[4];
+
+    {
+        let mut store = FeedStore::open(&path, key(&fixture.program_id)).unwrap();
+        for tx in [created, liked] {
+            store
+                .ingest_transaction(tx.slot, tx.block_time, &tx.signature, &tx.logs)
+                .unwrap();
+        }
+    }
+
+    // A subscriber restarting from an older slot sees the like again
+    let mut store = FeedStore::open(&path, key(&fixture.program_id)).unwrap();
+    let replayed = store
+        .ingest_transaction(liked.slot, liked.block_time, &liked.signature, &liked.logs)
+        .unwrap();
+    assert_eq!(replayed, 0);
+
+    let tweet = store.tweet(&key(ALICE_TWEET)).unwrap().unwrap();
+    assert_eq!(tweet.author, key(ALICE));
+    assert_eq!(tweet.likes(), 1);
+    let solana = store.tweets_by_topic("solana", &Page::first(10)).unwrap();
+    assert_eq!(tweet_keys(&solana), vec![key(ALICE_TWEET)]);
+
+    drop(store);
+    std::fs::remove_file(&path).unwrap();
+}
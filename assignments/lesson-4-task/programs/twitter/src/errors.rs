@@ -18,4 +18,110 @@ pub enum TwitterError {
     CommentTooLong,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Parent comment belongs to a different tweet")]
+    ParentCommentMismatch,
+    #[msg("Maximum comment depth reached")]
+    MaxCommentDepthReached,
+    #[msg("Reaction already has this type")]
+    SameReaction,
+    #[msg("Maximum number of Reactions Reached")]
+    MaxReactionsReached,
+    #[msg("Minimum number of Reactions Reached")]
+    MinReactionsReached,
+    #[msg("Maximum number of Edits Reached")]
+    MaxEditsReached,
+    #[msg("Maximum number of Tweets Reached")]
+    MaxTweetsReached,
+    #[msg("Account is not a legacy tweet")]
+    InvalidLegacyTweet,
+    #[msg("Handle too long")]
+    HandleTooLong,
+    #[msg("Handle may only contain lowercase letters, digits and underscores")]
+    InvalidHandle,
+    #[msg("Display name too long")]
+    DisplayNameTooLong,
+    #[msg("Bio too long")]
+    BioTooLong,
+    #[msg("Cannot follow yourself")]
+    CannotFollowSelf,
+    #[msg("Maximum number of Followers Reached")]
+    MaxFollowersReached,
+    #[msg("Minimum number of Followers Reached")]
+    MinFollowersReached,
+    #[msg("Maximum number of Following Reached")]
+    MaxFollowingReached,
+    #[msg("Minimum number of Following Reached")]
+    MinFollowingReached,
+    #[msg("Maximum number of Comments Reached")]
+    MaxCommentsReached,
+    #[msg("Comment belongs to a different tweet")]
+    CommentTweetMismatch,
+    #[msg("User is banned")]
+    UserBanned,
+    #[msg("Tip amount must be greater than zero")]
+    InvalidTipAmount,
+    #[msg("Fee cannot exceed 10000 basis points")]
+    InvalidFee,
+    #[msg("Token tips need the mint, both token accounts and the token program")]
+    MissingTokenAccounts,
+    #[msg("Maximum number of Tips Reached")]
+    MaxTipsReached,
+    #[msg("Treasury balance too low")]
+    InsufficientTreasuryBalance,
+    #[msg("Maximum number of Retweets Reached")]
+    MaxRetweetsReached,
+    #[msg("Minimum number of Retweets Reached")]
+    MinRetweetsReached,
+    #[msg("Cannot initialize, topic is empty")]
+    TopicEmpty,
+    #[msg("Cannot initialize, content is empty")]
+    ContentEmpty,
+    #[msg("Comment is empty")]
+    CommentEmpty,
+    #[msg("Parent tweet still exists")]
+    TweetStillExists,
+    #[msg("Token balance below the tweet's gate")]
+    TokenGateNotMet,
+    #[msg("Gated tweets need a minimum balance greater than zero")]
+    InvalidGateBalance,
+    #[msg("Gated tweets need the gate mint")]
+    MissingGateMint,
+    #[msg("Maximum number of Bookmarks Reached")]
+    MaxBookmarksReached,
+    #[msg("Tweet is already bookmarked")]
+    AlreadyBookmarked,
+    #[msg("Tweet is not bookmarked")]
+    NotBookmarked,
+    #[msg("Polls need between 2 and 4 options")]
+    InvalidPollOptionCount,
+    #[msg("Poll option is empty")]
+    PollOptionEmpty,
+    #[msg("Poll option too long")]
+    PollOptionTooLong,
+    #[msg("Poll must end in the future")]
+    InvalidPollEnd,
+    #[msg("Poll options and the poll account must be passed together")]
+    PollAccountMismatch,
+    #[msg("Poll has ended")]
+    PollEnded,
+    #[msg("Poll has not ended")]
+    PollNotEnded,
+    #[msg("Poll option does not exist")]
+    InvalidPollOption,
+    #[msg("Maximum number of Votes Reached")]
+    MaxVotesReached,
+    #[msg("Tweets must expire after they are published")]
+    InvalidSchedule,
+    #[msg("Tweet is not published yet")]
+    TweetNotPublished,
+    #[msg("Tweet has expired")]
+    TweetExpired,
+    #[msg("Tweet has not expired")]
+    TweetNotExpired,
+    #[msg("Too many hashtags and mentions")]
+    TooManyTags,
+    #[msg("Tag accounts do not match the hashtags and mentions of the tweet")]
+    TagAccountsMismatch,
+    #[msg("Account is not a legacy comment")]
+    InvalidLegacyComment,
 }
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::states::ReactionType;
+
+#[event]
+pub struct TipEvent {
+    pub tipper: Pubkey,
+    pub tweet: Pubkey,
+    pub tweet_author: Pubkey,
+    pub mint: Option<Pubkey>, // None for lamport tips
+    pub amount: u64, // full tip, including the fee
+    pub fee: u64,
+}
+
+#[event]
+pub struct InitializeTweetEvent {
+    pub tweet: Pubkey,
+    pub tweet_author: Pubkey,
+    pub index: u64,
+    pub topic: String,
+    pub content_hash: [u8; 32],
+    pub created_at: i64,
+}
+
+#[event]
+pub struct AddReactionEvent {
+    pub reaction_author: Pubkey,
+    pub tweet: Pubkey,
+    pub reaction: ReactionType,
+}
+
+#[event]
+pub struct RemoveReactionEvent {
+    pub reaction_author: Pubkey,
+    pub tweet: Pubkey,
+    pub reaction: ReactionType,
+}
+
+#[event]
+pub struct ChangeReactionEvent {
+    pub reaction_author: Pubkey,
+    pub tweet: Pubkey,
+    pub old: ReactionType,
+    pub new: ReactionType,
+}
+
+#[event]
+pub struct AddCommentEvent {
+    pub comment: Pubkey,
+    pub comment_author: Pubkey,
+    pub tweet: Pubkey,
+    pub id: u64,
+    pub parent_comment: Option<Pubkey>,
+    pub content_hash: [u8; 32],
+    pub created_at: i64,
+}
+
+#[event]
+pub struct RemoveCommentEvent {
+    pub comment: Pubkey,
+    pub comment_author: Pubkey,
+    pub tweet: Pubkey,
+    pub id: u64,
+}
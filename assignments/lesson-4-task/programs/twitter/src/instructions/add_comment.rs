@@ -3,24 +3,55 @@
 /// TASK: Implement the add comment functionality for the Twitter program
 /// 
 /// Requirements:
-/// - Validate that comment content doesn't exceed maximum length
+/// - Reject comments from banned users
+/// - On gated tweets, require a token account of the gate mint holding at
+///   least the tweet's min_balance
+/// - Reject comments before the tweet is published or after it expired
+/// - Validate that comment content is not blank and doesn't exceed maximum
+///   length, counted in characters
 /// - Initialize a new comment account with proper PDA seeds
 /// - Set comment fields: content, author, parent tweet, and bump
-/// - Use content hash in PDA seeds for unique comment identification
+/// - Use the tweet's comment counter in PDA seeds, giving each comment a
+///   sequential id and allowing identical comment text
+/// - Bump the tweet's comment counter
+/// - Replies pass a parent comment, which must belong to the same tweet
+///   and sit less than the max comment depth levels deep, as configured by
+///   the moderation admin (MAX_COMMENT_DEPTH until moderation is set up)
+/// - Emit an AddCommentEvent with the hash of the content
 /// 
 ///-------------------------------------------------------------------------------
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 use anchor_lang::solana_program::hash::hash;
 
 use crate::errors::TwitterError;
+use crate::events::AddCommentEvent;
 use crate::states::*;
 
 pub fn add_comment(ctx: Context<AddCommentContext>, comment_content: String) -> Result<()> {
-    // Validate comment content length
-    if comment_content.len() > COMMENT_LENGTH {
-        return err!(TwitterError::CommentTooLong);
-    }
+    // Validate comment content length in characters
+    Comment::validate_content(&comment_content)?;
+    
+    ctx.accounts.tweet.check_open(Clock::get()?.unix_timestamp)?;
+    ctx.accounts.tweet.check_gate(ctx.accounts.gate_token_account.as_deref().map(|account| &**account))?;
+    
+    // Replies sit one level below their parent, top-level comments at depth 0
+    let (parent_comment, depth) = match &ctx.accounts.parent_comment {
+        Some(parent) => {
+            require_keys_eq!(
+                parent.parent_tweet,
+                ctx.accounts.tweet.key(),
+                TwitterError::ParentCommentMismatch
+            );
+            let max_depth = ModerationConfig::max_comment_depth(&ctx.accounts.moderation_config)?;
+            if parent.depth >= max_depth {
+                return err!(TwitterError::MaxCommentDepthReached);
+            }
+            (Some(parent.key()), parent.depth + 1)
+        }
+        None => (None, 0),
+    };
     
     // Get the comment account from context
     let comment = &mut ctx.accounts.comment;
@@ -28,14 +59,35 @@ pub fn add_comment(ctx: Context<AddCommentContext>, comment_content: String) ->
     // Set comment fields
     comment.commentAuthor = ctx.accounts.comment_author.key();
     comment.parent_tweet = ctx.accounts.tweet.key();
-    comment.content = comment_content.clone();
+    comment.id = ctx.accounts.tweet.comment_count;
+    comment.content = comment_content;
+    comment.parent_comment = parent_comment;
+    comment.depth = depth;
+    comment.hidden = false;
     comment.bump = ctx.bumps.comment;
     
+    // Reserve the next id for the following comment on this tweet
+    let tweet = &mut ctx.accounts.tweet;
+    tweet.comment_count = tweet
+        .comment_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxCommentsReached)?;
+    
+    let comment = &ctx.accounts.comment;
+    emit!(AddCommentEvent {
+        comment: comment.key(),
+        comment_author: comment.commentAuthor,
+        tweet: comment.parent_tweet,
+        id: comment.id,
+        parent_comment: comment.parent_comment,
+        content_hash: hash(comment.content.as_bytes()).to_bytes(),
+        created_at: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(comment_content: String)]
 pub struct AddCommentContext<'info> {
     #[account(mut)]
     pub comment_author: Signer<'info>,
@@ -46,15 +98,38 @@ pub struct AddCommentContext<'info> {
         space = Comment::INIT_SPACE,
         seeds = [
             COMMENT_SEED.as_bytes(),
-            comment_author.key().as_ref(),
-            hash(comment_content.as_bytes()).to_bytes().as_ref(),
-            tweet.key().as_ref()
+            tweet.key().as_ref(),
+            tweet.comment_count.to_le_bytes().as_ref()
         ],
         bump
     )]
     pub comment: Box<Account<'info, Comment>>,
     
-    pub tweet: Account<'info, Tweet>,
+    #[account(mut)]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    pub parent_comment: Option<Box<Account<'info, Comment>>>,
+    
+    /// CHECK: ban record of the comment author, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            comment_author.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+    
+    /// CHECK: moderation config holding the max comment depth, may not be initialized yet
+    #[account(
+        seeds = [MODERATION_SEED.as_bytes()],
+        bump
+    )]
+    pub moderation_config: UncheckedAccount<'info>,
+    
+    #[account(token::authority = comment_author)]
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
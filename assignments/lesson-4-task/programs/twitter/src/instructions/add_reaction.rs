@@ -3,44 +3,46 @@
 /// TASK: Implement the add reaction functionality for the Twitter program
 /// 
 /// Requirements:
+/// - Reject reactions from banned users
+/// - On gated tweets, require a token account of the gate mint holding at
+///   least the tweet's min_balance
+/// - Reject reactions before the tweet is published or after it expired
 /// - Initialize a new reaction account with proper PDA seeds
-/// - Increment the appropriate counter (likes or dislikes) on the tweet
+/// - Increment the counter for the reaction type on the tweet
 /// - Set reaction fields: type, author, parent tweet, and bump
-/// - Handle both Like and Dislike reaction types
+/// - Handle every ReactionType variant the same way
+/// - Emit an AddReactionEvent
 /// 
 ///-------------------------------------------------------------------------------
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
 use crate::errors::TwitterError;
+use crate::events::AddReactionEvent;
 use crate::states::*;
 
 pub fn add_reaction(ctx: Context<AddReactionContext>, reaction: ReactionType) -> Result<()> {
+    ctx.accounts.tweet.check_open(Clock::get()?.unix_timestamp)?;
+    ctx.accounts.tweet.check_gate(ctx.accounts.gate_token_account.as_deref().map(|account| &**account))?;
+    
     // Get the reaction account from context
     let reaction_account = &mut ctx.accounts.tweet_reaction;
     
     // Set reaction account fields
     reaction_account.reactionAuthor = ctx.accounts.reaction_author.key();
     reaction_account.parent_tweet = ctx.accounts.tweet.key();
-    reaction_account.reaction = reaction.clone();
+    reaction_account.reaction = reaction;
     reaction_account.bump = ctx.bumps.tweet_reaction;
     
     // Update tweet counters
-    let tweet = &mut ctx.accounts.tweet;
-    match reaction {
-        ReactionType::Like => {
-            if tweet.likes == u64::MAX {
-                return err!(TwitterError::MaxLikesReached);
-            }
-            tweet.likes += 1;
-        },
-        ReactionType::Dislike => {
-            if tweet.dislikes == u64::MAX {
-                return err!(TwitterError::MaxDislikesReached);
-            }
-            tweet.dislikes += 1;
-        }
-    }
+    ctx.accounts.tweet.add_reaction(reaction)?;
+    
+    emit!(AddReactionEvent {
+        reaction_author: ctx.accounts.reaction_author.key(),
+        tweet: ctx.accounts.tweet.key(),
+        reaction,
+    });
     
     Ok(())
 }
@@ -66,5 +68,19 @@ pub struct AddReactionContext<'info> {
     #[account(mut)]
     pub tweet: Box<Account<'info, Tweet>>,
     
+    /// CHECK: ban record of the reaction author, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            reaction_author.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+    
+    #[account(token::authority = reaction_author)]
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,56 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the ban user functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the moderation admin can ban users
+/// - Initialize a ban account with proper PDA seeds for the banned user
+/// - Record who banned the user and when
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn add_ban(ctx: Context<BanUserContext>) -> Result<()> {
+    let ban = &mut ctx.accounts.ban;
+    
+    ban.user = ctx.accounts.user.key();
+    ban.banned_by = ctx.accounts.admin.key();
+    ban.banned_at = Clock::get()?.unix_timestamp;
+    ban.bump = ctx.bumps.ban;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BanUserContext<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [MODERATION_SEED.as_bytes()],
+        bump = moderation_config.bump,
+        has_one = admin @ TwitterError::Unauthorized
+    )]
+    pub moderation_config: Account<'info, ModerationConfig>,
+    
+    /// CHECK: any account can be banned, it is only used as a seed
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Ban::INIT_SPACE,
+        seeds = [
+            BAN_SEED.as_bytes(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub ban: Account<'info, Ban>,
+    
+    pub system_program: Program<'info, System>,
+}
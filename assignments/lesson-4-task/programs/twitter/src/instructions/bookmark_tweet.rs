@@ -0,0 +1,56 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the bookmark tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Grow the owner's bookmark list by one entry, the owner pays the extra rent
+/// - A tweet can only be bookmarked once
+/// - Keep at most MAX_BOOKMARKS tweets in the list
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn add_bookmark(ctx: Context<BookmarkTweetContext>) -> Result<()> {
+    let tweet = ctx.accounts.tweet.key();
+    let bookmark_list = &mut ctx.accounts.bookmark_list;
+    
+    require!(
+        bookmark_list.tweets.len() < MAX_BOOKMARKS,
+        TwitterError::MaxBookmarksReached
+    );
+    require!(
+        !bookmark_list.tweets.contains(&tweet),
+        TwitterError::AlreadyBookmarked
+    );
+    
+    bookmark_list.tweets.push(tweet);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BookmarkTweetContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            BOOKMARK_SEED.as_bytes(),
+            owner.key().as_ref()
+        ],
+        bump = bookmark_list.bump,
+        realloc = BookmarkList::space(bookmark_list.tweets.len() + 1),
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub bookmark_list: Account<'info, BookmarkList>,
+    
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,81 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the change reaction functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Reject reaction changes from banned users
+/// - Reject reaction changes on tweets that are not published yet or have expired
+/// - Verify that the tweet reaction exists and belongs to the reaction author
+/// - Switch the reaction type in place instead of closing and reopening it
+/// - Move one count from the old reaction type to the new one using checked math
+/// - Emit an event with the old and new reaction types for indexers
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::events::ChangeReactionEvent;
+use crate::states::*;
+
+pub fn update_reaction(ctx: Context<ChangeReactionContext>, reaction: ReactionType) -> Result<()> {
+    ctx.accounts.tweet.check_open(Clock::get()?.unix_timestamp)?;
+    
+    // Verify the reaction belongs to the author
+    let reaction_account = &mut ctx.accounts.tweet_reaction;
+    require!(
+        reaction_account.reactionAuthor == ctx.accounts.reaction_author.key(),
+        TwitterError::Unauthorized
+    );
+    require!(
+        reaction_account.reaction != reaction,
+        TwitterError::SameReaction
+    );
+    
+    // Move the count from the old reaction type to the new one
+    let old = reaction_account.reaction;
+    let tweet = &mut ctx.accounts.tweet;
+    tweet.remove_reaction(old)?;
+    tweet.add_reaction(reaction)?;
+    
+    reaction_account.reaction = reaction;
+    
+    emit!(ChangeReactionEvent {
+        reaction_author: ctx.accounts.reaction_author.key(),
+        tweet: ctx.accounts.tweet.key(),
+        old,
+        new: reaction,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ChangeReactionContext<'info> {
+    pub reaction_author: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            TWEET_REACTION_SEED.as_bytes(),
+            reaction_author.key().as_ref(),
+            tweet.key().as_ref()
+        ],
+        bump = tweet_reaction.bump
+    )]
+    pub tweet_reaction: Account<'info, Reaction>,
+    
+    #[account(mut)]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    /// CHECK: ban record of the reaction author, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            reaction_author.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+}
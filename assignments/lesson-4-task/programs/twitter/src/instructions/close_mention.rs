@@ -0,0 +1,50 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the close mention functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the owner of the mentioned profile can close the mention
+/// - Close the mention account and return rent to the tweet author, who paid
+///   for it
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn dismiss_mention(_ctx: Context<CloseMentionContext>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseMentionContext<'info> {
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            user.key().as_ref()
+        ],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    #[account(
+        mut,
+        close = tweet_author,
+        seeds = [
+            MENTION_SEED.as_bytes(),
+            mention.profile.as_ref(),
+            mention.tweet.as_ref()
+        ],
+        bump = mention.bump,
+        has_one = profile @ TwitterError::Unauthorized
+    )]
+    pub mention: Account<'info, Mention>,
+    
+    /// CHECK: receives the rent, pinned to the author of the tweet that created the mention
+    #[account(mut, address = mention.tweet_author)]
+    pub tweet_author: UncheckedAccount<'info>,
+}
@@ -0,0 +1,59 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the close poll functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Let the tweet author close the poll once it has ended
+/// - Let anyone close the poll once its tweet was deleted or expired
+/// - Close the poll account and return rent to the tweet author
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn close_finished_poll(ctx: Context<ClosePollContext>) -> Result<()> {
+    // Nobody can vote on a poll whose tweet is gone, so it can go right away
+    if ctx.accounts.tweet.data_is_empty() {
+        return Ok(());
+    }
+    
+    let poll = &ctx.accounts.poll;
+    require_keys_eq!(
+        ctx.accounts.closer.key(),
+        poll.author,
+        TwitterError::Unauthorized
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= poll.ends_at,
+        TwitterError::PollNotEnded
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClosePollContext<'info> {
+    pub closer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = author,
+        seeds = [
+            POLL_SEED.as_bytes(),
+            poll.tweet.as_ref()
+        ],
+        bump = poll.bump
+    )]
+    pub poll: Box<Account<'info, Poll>>,
+    
+    /// CHECK: receives the rent, pinned to the poll's author
+    #[account(mut, address = poll.author)]
+    pub author: UncheckedAccount<'info>,
+    
+    /// CHECK: the poll's tweet, which may have been deleted or expired
+    #[account(address = poll.tweet)]
+    pub tweet: UncheckedAccount<'info>,
+}
@@ -0,0 +1,42 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the create bookmark list functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Initialize an empty bookmark list with proper PDA seeds, one per user
+/// - Set bookmark list fields: owner, tweets, and bump
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::states::*;
+
+pub fn initialize_bookmark_list(ctx: Context<CreateBookmarkListContext>) -> Result<()> {
+    let bookmark_list = &mut ctx.accounts.bookmark_list;
+    bookmark_list.owner = ctx.accounts.owner.key();
+    bookmark_list.tweets = Vec::new();
+    bookmark_list.bump = ctx.bumps.bookmark_list;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateBookmarkListContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = BookmarkList::space(0),
+        seeds = [
+            BOOKMARK_SEED.as_bytes(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub bookmark_list: Account<'info, BookmarkList>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,94 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the create profile functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Validate handle, display name and bio lengths
+/// - Handles are lowercase letters, digits and underscores only
+/// - Claim the handle with a registry PDA seeded by the handle, so each
+///   handle can belong to one profile only
+/// - Initialize a profile account for the signer with proper PDA seeds
+/// - Start the tweet and follow counters at zero
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn initialize_profile(
+    ctx: Context<CreateProfileContext>,
+    handle: String,
+    display_name: String,
+    bio: String,
+) -> Result<()> {
+    // Validate handle
+    if handle.len() > HANDLE_LENGTH {
+        return err!(TwitterError::HandleTooLong);
+    }
+    require!(
+        !handle.is_empty()
+            && handle
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_'),
+        TwitterError::InvalidHandle
+    );
+    
+    // Validate display name and bio lengths
+    if display_name.len() > DISPLAY_NAME_LENGTH {
+        return err!(TwitterError::DisplayNameTooLong);
+    }
+    if bio.len() > BIO_LENGTH {
+        return err!(TwitterError::BioTooLong);
+    }
+    
+    let handle_registry = &mut ctx.accounts.handle_registry;
+    handle_registry.profile = ctx.accounts.profile.key();
+    handle_registry.bump = ctx.bumps.handle_registry;
+    
+    let profile = &mut ctx.accounts.profile;
+    profile.authority = ctx.accounts.authority.key();
+    profile.handle = handle;
+    profile.display_name = display_name;
+    profile.bio = bio;
+    profile.tweet_count = 0;
+    profile.follower_count = 0;
+    profile.following_count = 0;
+    profile.bump = ctx.bumps.profile;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct CreateProfileContext<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            authority.key().as_ref()
+        ],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HandleRegistry::INIT_SPACE,
+        seeds = [
+            HANDLE_SEED.as_bytes(),
+            handle.as_bytes()
+        ],
+        bump
+    )]
+    pub handle_registry: Account<'info, HandleRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
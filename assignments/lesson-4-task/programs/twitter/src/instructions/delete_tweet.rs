@@ -0,0 +1,40 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the delete tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the tweet author can delete the tweet
+/// - Close the tweet account and return rent to the tweet author
+/// 
+/// NOTE: Reactions and comments keep pointing at the closed tweet through
+/// parent_tweet. Tweets are seeded by the profile's tweet_count, which only
+/// grows, so a later tweet never reuses the address and they can't attach to
+/// it. Once the tweet account is gone they are no longer valid and can be
+/// closed by anyone through reap_reaction and reap_comment, with the rent
+/// going back to their authors. A poll on the tweet goes the same way through
+/// close_poll and reap_poll_vote, its hashtag indexes through
+/// reap_tag_index, and retweets and quote tweets of it through reap_retweet.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn remove_tweet(_ctx: Context<DeleteTweetContext>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DeleteTweetContext<'info> {
+    #[account(mut)]
+    pub tweet_author: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = tweet_author,
+        has_one = tweet_author @ TwitterError::Unauthorized
+    )]
+    pub tweet: Box<Account<'info, Tweet>>,
+}
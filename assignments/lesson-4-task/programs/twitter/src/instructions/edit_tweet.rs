@@ -0,0 +1,64 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the edit tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the tweet author can edit the tweet
+/// - Reject edits from banned users
+/// - Validate that the new content is not blank and doesn't exceed maximum
+///   length, counted in characters
+/// - Store the hash of the content being replaced
+/// - Gated tweets only edit their public content, the off-chain content hash
+///   stays as is
+/// - Bump the edit counter and record when the edit happened
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn update_tweet(ctx: Context<EditTweetContext>, content: String) -> Result<()> {
+    // Validate content length in characters
+    Tweet::validate_content(&content)?;
+    
+    let tweet = &mut ctx.accounts.tweet;
+    
+    // Keep a fingerprint of the old content so edits stay verifiable
+    tweet.previous_content_hash = hash(tweet.content.as_bytes()).to_bytes();
+    tweet.content = content;
+    if tweet.gate_mint.is_none() {
+        tweet.content_hash = hash(tweet.content.as_bytes()).to_bytes();
+    }
+    tweet.edit_count = tweet
+        .edit_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxEditsReached)?;
+    tweet.last_edited_at = Clock::get()?.unix_timestamp;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EditTweetContext<'info> {
+    pub tweet_author: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = tweet_author @ TwitterError::Unauthorized
+    )]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    /// CHECK: ban record of the tweet author, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            tweet_author.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+}
@@ -0,0 +1,43 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the expire tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Anyone can expire a tweet once its expires_at has passed
+/// - Tweets without an expiry cannot be expired
+/// - Close the tweet account and return rent to the tweet author
+/// 
+/// NOTE: Like delete_tweet, reactions, comments, a poll, hashtag indexes and
+/// retweets left on the tweet can be closed afterwards through reap_reaction,
+/// reap_comment, close_poll, reap_tag_index and reap_retweet.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn close_expired_tweet(ctx: Context<ExpireTweetContext>) -> Result<()> {
+    require!(
+        ctx.accounts.tweet.is_expired(Clock::get()?.unix_timestamp),
+        TwitterError::TweetNotExpired
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireTweetContext<'info> {
+    pub closer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = tweet_author
+    )]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    /// CHECK: receives the rent, pinned to the tweet's author
+    #[account(mut, address = tweet.tweet_author)]
+    pub tweet_author: UncheckedAccount<'info>,
+}
@@ -0,0 +1,82 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the follow functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Users cannot follow themselves
+/// - Initialize a follow account with proper PDA seeds, so a user can only
+///   follow the same profile once
+/// - Increment following on the follower and followers on the followed profile
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn follow_user(ctx: Context<FollowContext>) -> Result<()> {
+    let follower = ctx.accounts.follower.key();
+    let followed = ctx.accounts.followed_profile.authority;
+    require_keys_neq!(follower, followed, TwitterError::CannotFollowSelf);
+    
+    let follow = &mut ctx.accounts.follow;
+    follow.follower = follower;
+    follow.followed = followed;
+    follow.bump = ctx.bumps.follow;
+    
+    let follower_profile = &mut ctx.accounts.follower_profile;
+    follower_profile.following_count = follower_profile
+        .following_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxFollowingReached)?;
+    
+    let followed_profile = &mut ctx.accounts.followed_profile;
+    followed_profile.follower_count = followed_profile
+        .follower_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxFollowersReached)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FollowContext<'info> {
+    #[account(mut)]
+    pub follower: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            follower.key().as_ref()
+        ],
+        bump = follower_profile.bump
+    )]
+    pub follower_profile: Box<Account<'info, Profile>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            followed_profile.authority.as_ref()
+        ],
+        bump = followed_profile.bump
+    )]
+    pub followed_profile: Box<Account<'info, Profile>>,
+    
+    #[account(
+        init,
+        payer = follower,
+        space = 8 + Follow::INIT_SPACE,
+        seeds = [
+            FOLLOW_SEED.as_bytes(),
+            follower.key().as_ref(),
+            followed_profile.authority.as_ref()
+        ],
+        bump
+    )]
+    pub follow: Account<'info, Follow>,
+    
+    pub system_program: Program<'info, System>,
+}
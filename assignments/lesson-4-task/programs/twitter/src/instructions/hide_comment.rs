@@ -0,0 +1,35 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the hide comment functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the author of the tweet can hide or unhide comments on it
+/// - The comment must belong to that tweet
+/// - Hidden comments stay on chain, clients are expected to skip them
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn set_comment_hidden(ctx: Context<HideCommentContext>, hidden: bool) -> Result<()> {
+    ctx.accounts.comment.hidden = hidden;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct HideCommentContext<'info> {
+    pub tweet_author: Signer<'info>,
+    
+    #[account(has_one = tweet_author @ TwitterError::Unauthorized)]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    #[account(
+        mut,
+        constraint = comment.parent_tweet == tweet.key() @ TwitterError::CommentTweetMismatch
+    )]
+    pub comment: Account<'info, Comment>,
+}
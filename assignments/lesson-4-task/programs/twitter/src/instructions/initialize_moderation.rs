@@ -0,0 +1,51 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the initialize moderation functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the program's upgrade authority can set up moderation
+/// - Initialize the single moderation config PDA with the admin key
+/// - Start with the default MAX_COMMENT_DEPTH for replies
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::program::Twitter;
+use crate::states::*;
+
+pub fn create_moderation_config(ctx: Context<InitializeModerationContext>, admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.moderation_config;
+    
+    config.admin = admin;
+    config.max_comment_depth = MAX_COMMENT_DEPTH;
+    config.bump = ctx.bumps.moderation_config;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeModerationContext<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ModerationConfig::INIT_SPACE,
+        seeds = [MODERATION_SEED.as_bytes()],
+        bump
+    )]
+    pub moderation_config: Account<'info, ModerationConfig>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Twitter>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ TwitterError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,54 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the initialize treasury functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the program's upgrade authority can set up the treasury
+/// - Validate the protocol fee in basis points
+/// - Initialize the single treasury PDA, a fee of zero disables the fee
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::program::Twitter;
+use crate::states::*;
+
+pub fn create_treasury(ctx: Context<InitializeTreasuryContext>, fee_bps: u16) -> Result<()> {
+    if fee_bps > MAX_FEE_BPS {
+        return err!(TwitterError::InvalidFee);
+    }
+    
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.authority = ctx.accounts.authority.key();
+    treasury.fee_bps = fee_bps;
+    treasury.bump = ctx.bumps.treasury;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryContext<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Twitter>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ TwitterError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -3,33 +3,86 @@
 /// TASK: Implement the initialize tweet functionality for the Twitter program
 /// 
 /// Requirements:
-/// - Validate that topic and content don't exceed maximum lengths
+/// - Reject tweets from banned users
+/// - Validate that topic and content are not blank and don't exceed maximum
+///   lengths, counted in characters
+/// - NFC normalize the topic before storing it
 /// - Initialize a new tweet account with proper PDA seeds
-/// - Set tweet fields: topic, content, author, likes, dislikes, and bump
-/// - Initialize counters (likes and dislikes) to zero
-/// - Use topic in PDA seeds for tweet identification
+/// - Set tweet fields: topic, content, author, reaction counts, and bump
+/// - Initialize every reaction counter to zero
+/// - Use the author's profile tweet counter in PDA seeds, so an author can
+///   post any number of tweets under the same topic
+/// - Bump the profile tweet counter
+/// - Take the optional features below in one TweetOptions argument, so a
+///   tweet can combine a gate, a poll and a schedule
+/// - Gated tweets keep their full content off-chain, store its hash and
+///   require commenters and reactors to hold min_balance of the gate mint
+/// - Optionally attach a poll with 2 to MAX_POLL_OPTIONS options and an end
+///   time in the future, in its own PDA next to the tweet
+/// - Optionally schedule the tweet: reactions and comments are only accepted
+///   from publish_at (now by default) until expires_at (never by default)
+/// - Parse #hashtags and @handles from the content, at most MAX_TAGS per tweet
+/// - When the client passes them as remaining accounts, index each hashtag in
+///   a TagIndex and notify each mentioned profile with a Mention: one
+///   TagIndex per hashtag, then a HandleRegistry and Mention pair per mention,
+///   in order of appearance
+/// - NFC normalize hashtags before hashing them, like topics
+/// - Still create tag accounts whose address someone funded beforehand
+/// - Emit an InitializeTweetEvent with the hash of the content
 /// 
 ///-------------------------------------------------------------------------------
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+use anchor_spl::token_interface::Mint;
 
 use crate::errors::TwitterError;
+use crate::events::InitializeTweetEvent;
 use crate::states::*;
 
-pub fn initialize_tweet(
-    ctx: Context<InitializeTweet>,
+pub fn initialize_tweet<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializeTweet<'info>>,
     topic: String,
     content: String,
+    options: TweetOptions,
 ) -> Result<()> {
-    // Validate topic length
-    if topic.len() > TOPIC_LENGTH {
-        return err!(TwitterError::TopicTooLong);
-    }
+    let TweetOptions { gate, poll, schedule } = options;
     
-    // Validate content length
-    if content.len() > CONTENT_LENGTH {
-        return err!(TwitterError::ContentTooLong);
-    }
+    // Normalize the topic and validate both lengths in characters
+    let topic = Tweet::normalize_topic(&topic)?;
+    Tweet::validate_content(&content)?;
+    
+    let tags = Tag::parse(&content);
+    require!(tags.len() <= MAX_TAGS, TwitterError::TooManyTags);
+    
+    // Public tweets hash their own content, gated ones the off-chain content
+    let (content_hash, gate_mint, min_balance) = match gate {
+        Some(gate) => {
+            require!(gate.min_balance > 0, TwitterError::InvalidGateBalance);
+            let Some(gate_mint) = &ctx.accounts.gate_mint else {
+                return err!(TwitterError::MissingGateMint);
+            };
+            (gate.content_hash, Some(gate_mint.key()), gate.min_balance)
+        }
+        None => (hash(content.as_bytes()).to_bytes(), None, 0),
+    };
+    
+    let now = Clock::get()?.unix_timestamp;
+    let schedule = schedule.unwrap_or_default();
+    let publish_at = schedule.publish_at.unwrap_or(now);
+    let expires_at = match schedule.expires_at {
+        Some(expires_at) => {
+            require!(
+                expires_at > publish_at.max(now),
+                TwitterError::InvalidSchedule
+            );
+            expires_at
+        }
+        None => 0,
+    };
     
     // Get the tweet account from context
     let tweet = &mut ctx.accounts.tweet;
@@ -38,31 +91,295 @@ pub fn initialize_tweet(
     tweet.tweet_author = ctx.accounts.tweet_authority.key();
     tweet.topic = topic;
     tweet.content = content;
-    tweet.likes = 0;
-    tweet.dislikes = 0;
+    tweet.reaction_counts = [0; ReactionType::COUNT];
+    tweet.edit_count = 0;
+    tweet.last_edited_at = 0;
+    tweet.previous_content_hash = [0; 32];
+    tweet.index = ctx.accounts.profile.tweet_count;
+    tweet.comment_count = 0;
+    tweet.total_tips = 0;
+    tweet.retweet_count = 0;
+    tweet.content_hash = content_hash;
+    tweet.gate_mint = gate_mint;
+    tweet.min_balance = min_balance;
+    tweet.publish_at = publish_at;
+    tweet.expires_at = expires_at;
     tweet.bump = ctx.bumps.tweet;
     
+    // Reserve the next index for the author's following tweet
+    let profile = &mut ctx.accounts.profile;
+    profile.tweet_count = profile
+        .tweet_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxTweetsReached)?;
+    
+    // The poll account is only created when options are given
+    match (poll, &mut ctx.accounts.poll) {
+        (Some(config), Some(poll)) => {
+            Poll::validate_options(&config.options)?;
+            require!(
+                config.ends_at > now,
+                TwitterError::InvalidPollEnd
+            );
+            poll.tweet = ctx.accounts.tweet.key();
+            poll.author = ctx.accounts.tweet_authority.key();
+            poll.options = config.options;
+            poll.votes = [0; MAX_POLL_OPTIONS];
+            poll.ends_at = config.ends_at;
+            poll.bump = ctx.bumps.poll.ok_or(TwitterError::PollAccountMismatch)?;
+        }
+        (None, None) => {}
+        _ => return err!(TwitterError::PollAccountMismatch),
+    }
+    
+    // Tag accounts are optional, tweets without them are just not indexed
+    if !ctx.remaining_accounts.is_empty() {
+        index_tags(ctx.accounts, ctx.remaining_accounts, &tags, ctx.program_id)?;
+    }
+    
+    let tweet = &ctx.accounts.tweet;
+    emit!(InitializeTweetEvent {
+        tweet: tweet.key(),
+        tweet_author: tweet.tweet_author,
+        index: tweet.index,
+        topic: tweet.topic.clone(),
+        content_hash: tweet.content_hash,
+        created_at: now,
+    });
+    
+    Ok(())
+}
+
+fn index_tags<'info>(
+    accounts: &InitializeTweet<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    tags: &[Tag],
+    program_id: &Pubkey,
+) -> Result<()> {
+    let expected: usize = tags
+        .iter()
+        .map(|tag| match tag {
+            Tag::Hashtag(_) => 1,
+            Tag::Mention(_) => 2,
+        })
+        .sum();
+    require!(
+        remaining_accounts.len() == expected,
+        TwitterError::TagAccountsMismatch
+    );
+    
+    let tweet = accounts.tweet.key();
+    let mut remaining = remaining_accounts.iter();
+    for tag in tags {
+        match tag {
+            Tag::Hashtag(tag) => {
+                let tag_hash = hash(tag.as_bytes()).to_bytes();
+                let target = remaining.next().ok_or(TwitterError::TagAccountsMismatch)?;
+                create_tag_account(
+                    accounts,
+                    target,
+                    &[TAG_SEED.as_bytes(), tag_hash.as_ref(), tweet.as_ref()],
+                    8 + TagIndex::INIT_SPACE,
+                    program_id,
+                    |bump| TagIndex {
+                        tag_hash,
+                        tweet,
+                        tweet_author: accounts.tweet_authority.key(),
+                        bump,
+                    },
+                )?;
+            }
+            Tag::Mention(handle) => {
+                let registry_info = remaining.next().ok_or(TwitterError::TagAccountsMismatch)?;
+                let target = remaining.next().ok_or(TwitterError::TagAccountsMismatch)?;
+                let (registry_address, _) =
+                    Pubkey::find_program_address(&[HANDLE_SEED.as_bytes(), handle.as_bytes()], program_id);
+                require_keys_eq!(
+                    registry_info.key(),
+                    registry_address,
+                    TwitterError::TagAccountsMismatch
+                );
+                
+                // Nobody to notify for handles that were never claimed
+                if registry_info.owner != program_id {
+                    continue;
+                }
+                let profile = HandleRegistry::try_deserialize(&mut &registry_info.try_borrow_data()?[..])?.profile;
+                create_tag_account(
+                    accounts,
+                    target,
+                    &[MENTION_SEED.as_bytes(), profile.as_ref(), tweet.as_ref()],
+                    8 + Mention::INIT_SPACE,
+                    program_id,
+                    |bump| Mention {
+                        profile,
+                        tweet,
+                        tweet_author: accounts.tweet_authority.key(),
+                        bump,
+                    },
+                )?;
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Creates `target` at the PDA of `seeds`, paid by the tweet author, and writes `state` into it.
+/// Tag addresses are predictable, so a target someone already funded is topped up to rent
+/// exemption, allocated and assigned instead, as `create_account` would reject it
+fn create_tag_account<'info, T: AccountSerialize>(
+    accounts: &InitializeTweet<'info>,
+    target: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    program_id: &Pubkey,
+    state: impl FnOnce(u8) -> T,
+) -> Result<()> {
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    require_keys_eq!(target.key(), address, TwitterError::TagAccountsMismatch);
+    
+    let bump_seed = [bump];
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&bump_seed);
+    let system_program = accounts.system_program.to_account_info();
+    let rent = Rent::get()?.minimum_balance(space);
+    
+    if target.lamports() == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount {
+                    from: accounts.tweet_authority.to_account_info(),
+                    to: target.clone(),
+                },
+                &[&signer_seeds],
+            ),
+            rent,
+            space as u64,
+            program_id,
+        )?;
+    } else {
+        let shortfall = rent.saturating_sub(target.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: accounts.tweet_authority.to_account_info(),
+                        to: target.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: target.clone(),
+                },
+                &[&signer_seeds],
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: target.clone(),
+                },
+                &[&signer_seeds],
+            ),
+            program_id,
+        )?;
+    }
+    
+    state(bump).try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
     Ok(())
 }
 
+/// Optional features of a new tweet, a plain tweet leaves all of them `None`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TweetOptions {
+    pub gate: Option<TokenGate>,
+    pub poll: Option<PollConfig>,
+    pub schedule: Option<Schedule>,
+}
+
+/// Off-chain content of a gated tweet and the balance needed to interact with it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenGate {
+    pub content_hash: [u8; 32],
+    pub min_balance: u64,
+}
+
+/// Options and end time of a poll attached to a new tweet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PollConfig {
+    pub options: Vec<String>,
+    pub ends_at: i64,
+}
+
+/// When a scheduled tweet opens for reactions and comments, and when it expires
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Schedule {
+    pub publish_at: Option<i64>,
+    pub expires_at: Option<i64>,
+}
+
 #[derive(Accounts)]
-#[instruction(topic: String)]
 pub struct InitializeTweet<'info> {
     #[account(mut)]
     pub tweet_authority: Signer<'info>,
     
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            tweet_authority.key().as_ref()
+        ],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, Profile>,
+    
     #[account(
         init,
         payer = tweet_authority,
-        space = Tweet::INIT_SPACE,
+        space = 8 + Tweet::INIT_SPACE,
         seeds = [
-            topic.as_bytes(),
             TWEET_SEED.as_bytes(),
-            tweet_authority.key().as_ref()
+            tweet_authority.key().as_ref(),
+            profile.tweet_count.to_le_bytes().as_ref()
         ],
         bump
     )]
     pub tweet: Box<Account<'info, Tweet>>,
     
+    /// CHECK: ban record of the tweet authority, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            tweet_authority.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+    
+    pub gate_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        init,
+        payer = tweet_authority,
+        space = 8 + Poll::INIT_SPACE,
+        seeds = [
+            POLL_SEED.as_bytes(),
+            tweet.key().as_ref()
+        ],
+        bump
+    )]
+    pub poll: Option<Box<Account<'info, Poll>>>,
+    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,117 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the migrate tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Read a tweet created at the old [topic, TWEET_SEED, author] address
+/// - Only the tweet author can migrate it
+/// - Recreate it at the next [TWEET_SEED, author, index] address, with the
+///   topic NFC normalized like new tweets
+/// - Start the reaction counters at 0
+/// - Close the old account and return its rent to the author
+/// 
+/// NOTE: Reaction and comment accounts are tied to the old tweet address and
+/// are not carried over, so the legacy likes and dislikes are dropped rather
+/// than copied into counters no reaction account could ever decrement. The
+/// old reactions and comments can't be removed through the migrated tweet,
+/// anyone can close them with reap_reaction and reap_legacy_comment instead.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn migrate_legacy_tweet(ctx: Context<MigrateTweetContext>, topic: String) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_tweet.to_account_info();
+    
+    // The old layout shares the Tweet discriminator, so check it by hand
+    let legacy = {
+        let data = legacy_info.try_borrow_data()?;
+        if legacy_info.owner != ctx.program_id || !data.starts_with(Tweet::DISCRIMINATOR) {
+            return err!(TwitterError::InvalidLegacyTweet);
+        }
+        LegacyTweet::deserialize(&mut &data[Tweet::DISCRIMINATOR.len()..])
+            .map_err(|_| error!(TwitterError::InvalidLegacyTweet))?
+    };
+    require!(legacy.topic == topic, TwitterError::InvalidLegacyTweet);
+    require_keys_eq!(
+        legacy.tweet_author,
+        ctx.accounts.tweet_authority.key(),
+        TwitterError::Unauthorized
+    );
+    
+    let tweet = &mut ctx.accounts.tweet;
+    tweet.tweet_author = legacy.tweet_author;
+    tweet.topic = legacy.topic.nfc().collect();
+    tweet.content = legacy.content;
+    tweet.reaction_counts = [0; ReactionType::COUNT];
+    tweet.edit_count = 0;
+    tweet.last_edited_at = 0;
+    tweet.previous_content_hash = [0; 32];
+    tweet.index = ctx.accounts.profile.tweet_count;
+    tweet.comment_count = 0;
+    tweet.total_tips = 0;
+    tweet.retweet_count = 0;
+    tweet.content_hash = hash(tweet.content.as_bytes()).to_bytes();
+    tweet.gate_mint = None;
+    tweet.min_balance = 0;
+    tweet.publish_at = Clock::get()?.unix_timestamp;
+    tweet.expires_at = 0;
+    tweet.bump = ctx.bumps.tweet;
+    
+    let profile = &mut ctx.accounts.profile;
+    profile.tweet_count = profile
+        .tweet_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxTweetsReached)?;
+    
+    close_legacy_account(&legacy_info, &ctx.accounts.tweet_authority.to_account_info())
+}
+
+#[derive(Accounts)]
+#[instruction(topic: String)]
+pub struct MigrateTweetContext<'info> {
+    #[account(mut)]
+    pub tweet_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            tweet_authority.key().as_ref()
+        ],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    /// CHECK: legacy tweet layout, validated against the old seeds and decoded in the handler
+    #[account(
+        mut,
+        seeds = [
+            topic.as_bytes(),
+            TWEET_SEED.as_bytes(),
+            tweet_authority.key().as_ref()
+        ],
+        bump
+    )]
+    pub legacy_tweet: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = tweet_authority,
+        space = 8 + Tweet::INIT_SPACE,
+        seeds = [
+            TWEET_SEED.as_bytes(),
+            tweet_authority.key().as_ref(),
+            profile.tweet_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -1,14 +1,104 @@
+pub use create_profile::*;
+pub mod create_profile;
+
+pub use follow::*;
+pub mod follow;
+
+pub use unfollow::*;
+pub mod unfollow;
+
 pub use initialize_tweet::*;
 pub mod initialize_tweet;
 
+pub use edit_tweet::*;
+pub mod edit_tweet;
+
+pub use delete_tweet::*;
+pub mod delete_tweet;
+
+pub use expire_tweet::*;
+pub mod expire_tweet;
+
+pub use migrate_tweet::*;
+pub mod migrate_tweet;
+
 pub use add_reaction::*;
 pub mod add_reaction;
 
 pub use remove_reaction::*;
 pub mod remove_reaction;
 
+pub use change_reaction::*;
+pub mod change_reaction;
+
 pub use add_comment::*;
 pub mod add_comment;
 
 pub use remove_comment::*;
 pub mod remove_comment;
+
+pub use retweet::*;
+pub mod retweet;
+
+pub use undo_retweet::*;
+pub mod undo_retweet;
+
+pub use reap_retweet::*;
+pub mod reap_retweet;
+
+pub use vote_poll::*;
+pub mod vote_poll;
+
+pub use close_poll::*;
+pub mod close_poll;
+
+pub use reap_poll_vote::*;
+pub mod reap_poll_vote;
+
+pub use reap_reaction::*;
+pub mod reap_reaction;
+
+pub use reap_comment::*;
+pub mod reap_comment;
+
+pub use reap_legacy_comment::*;
+pub mod reap_legacy_comment;
+
+pub use create_bookmark_list::*;
+pub mod create_bookmark_list;
+
+pub use bookmark_tweet::*;
+pub mod bookmark_tweet;
+
+pub use unbookmark_tweet::*;
+pub mod unbookmark_tweet;
+
+pub use close_mention::*;
+pub mod close_mention;
+
+pub use reap_tag_index::*;
+pub mod reap_tag_index;
+
+pub use hide_comment::*;
+pub mod hide_comment;
+
+pub use initialize_moderation::*;
+pub mod initialize_moderation;
+
+pub use set_comment_depth::*;
+pub mod set_comment_depth;
+
+pub use ban_user::*;
+pub mod ban_user;
+
+pub use unban_user::*;
+pub mod unban_user;
+
+pub use initialize_treasury::*;
+pub mod initialize_treasury;
+
+pub use tip_tweet::*;
+pub mod tip_tweet;
+
+pub use withdraw_treasury::*;
+pub mod withdraw_treasury;
@@ -0,0 +1,56 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the reap comment functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Anyone can reap a comment once its parent tweet account is gone
+/// - Close the comment account and return rent to the comment author
+/// - Emit a RemoveCommentEvent
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::events::RemoveCommentEvent;
+use crate::states::*;
+
+pub fn reap_orphaned_comment(ctx: Context<ReapCommentContext>) -> Result<()> {
+    let comment = &ctx.accounts.comment;
+    emit!(RemoveCommentEvent {
+        comment: comment.key(),
+        comment_author: comment.commentAuthor,
+        tweet: comment.parent_tweet,
+        id: comment.id,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReapCommentContext<'info> {
+    pub reaper: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = comment_author,
+        seeds = [
+            COMMENT_SEED.as_bytes(),
+            comment.parent_tweet.as_ref(),
+            comment.id.to_le_bytes().as_ref()
+        ],
+        bump = comment.bump
+    )]
+    pub comment: Box<Account<'info, Comment>>,
+    
+    /// CHECK: receives the rent, pinned to the comment's author
+    #[account(mut, address = comment.commentAuthor)]
+    pub comment_author: UncheckedAccount<'info>,
+    
+    /// CHECK: the comment's parent tweet, which must have been closed
+    #[account(
+        address = comment.parent_tweet,
+        constraint = tweet.data_is_empty() @ TwitterError::TweetStillExists
+    )]
+    pub tweet: UncheckedAccount<'info>,
+}
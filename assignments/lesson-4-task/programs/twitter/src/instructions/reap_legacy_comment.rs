@@ -0,0 +1,70 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the reap legacy comment functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Read a comment created at the old [COMMENT_SEED, author, hash(content), tweet]
+///   address, before comments had ids and replies
+/// - Anyone can reap it once its parent tweet account is gone, e.g. after the
+///   tweet was migrated or deleted
+/// - Close the comment account and return rent to the comment author
+/// 
+/// NOTE: Legacy comments have no id, so no RemoveCommentEvent is emitted for
+/// them. The indexer never saw an AddCommentEvent for them either.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn reap_orphaned_legacy_comment(ctx: Context<ReapLegacyCommentContext>, content: String) -> Result<()> {
+    let comment_info = ctx.accounts.comment.to_account_info();
+    
+    // The old layout shares the Comment discriminator, so check it by hand
+    let legacy = {
+        let data = comment_info.try_borrow_data()?;
+        if comment_info.owner != ctx.program_id || !data.starts_with(Comment::DISCRIMINATOR) {
+            return err!(TwitterError::InvalidLegacyComment);
+        }
+        LegacyComment::deserialize(&mut &data[Comment::DISCRIMINATOR.len()..])
+            .map_err(|_| error!(TwitterError::InvalidLegacyComment))?
+    };
+    require!(
+        legacy.commentAuthor == ctx.accounts.comment_author.key()
+            && legacy.parent_tweet == ctx.accounts.tweet.key()
+            && legacy.content == content,
+        TwitterError::InvalidLegacyComment
+    );
+    
+    close_legacy_account(&comment_info, &ctx.accounts.comment_author.to_account_info())
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
+pub struct ReapLegacyCommentContext<'info> {
+    pub reaper: Signer<'info>,
+    
+    /// CHECK: legacy comment layout, validated against the old seeds and decoded in the handler
+    #[account(
+        mut,
+        seeds = [
+            COMMENT_SEED.as_bytes(),
+            comment_author.key().as_ref(),
+            hash(content.as_bytes()).to_bytes().as_ref(),
+            tweet.key().as_ref()
+        ],
+        bump
+    )]
+    pub comment: UncheckedAccount<'info>,
+    
+    /// CHECK: receives the rent, checked against the author stored in the comment
+    #[account(mut)]
+    pub comment_author: UncheckedAccount<'info>,
+    
+    /// CHECK: the comment's parent tweet, which must have been closed
+    #[account(constraint = tweet.data_is_empty() @ TwitterError::TweetStillExists)]
+    pub tweet: UncheckedAccount<'info>,
+}
@@ -0,0 +1,57 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the reap poll vote functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Anyone can reap a vote once its poll has ended or was closed
+/// - Close the vote account and return rent to the voter
+/// 
+/// NOTE: The vote stays counted in the poll's tally. The vote account only
+/// stops a second vote, which an ended or closed poll rejects anyway.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn reap_finished_poll_vote(ctx: Context<ReapPollVoteContext>) -> Result<()> {
+    let poll = &ctx.accounts.poll;
+    if poll.data_is_empty() {
+        return Ok(());
+    }
+    
+    let poll = Poll::try_deserialize(&mut &poll.try_borrow_data()?[..])?;
+    require!(
+        Clock::get()?.unix_timestamp >= poll.ends_at,
+        TwitterError::PollNotEnded
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReapPollVoteContext<'info> {
+    pub reaper: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            POLL_VOTE_SEED.as_bytes(),
+            poll_vote.poll.as_ref(),
+            poll_vote.voter.as_ref()
+        ],
+        bump = poll_vote.bump
+    )]
+    pub poll_vote: Account<'info, PollVote>,
+    
+    /// CHECK: receives the rent, pinned to the vote's voter
+    #[account(mut, address = poll_vote.voter)]
+    pub voter: UncheckedAccount<'info>,
+    
+    /// CHECK: the vote's poll, read only while it still exists
+    #[account(address = poll_vote.poll)]
+    pub poll: UncheckedAccount<'info>,
+}
@@ -0,0 +1,58 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the reap reaction functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Anyone can reap a reaction once its parent tweet account is gone
+/// - Close the reaction account and return rent to the reaction author
+/// - Emit a RemoveReactionEvent
+/// 
+/// NOTE: The tweet's reaction counters are not touched, they were closed
+/// together with the tweet.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::events::RemoveReactionEvent;
+use crate::states::*;
+
+pub fn reap_orphaned_reaction(ctx: Context<ReapReactionContext>) -> Result<()> {
+    let reaction = &ctx.accounts.tweet_reaction;
+    emit!(RemoveReactionEvent {
+        reaction_author: reaction.reactionAuthor,
+        tweet: reaction.parent_tweet,
+        reaction: reaction.reaction,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReapReactionContext<'info> {
+    pub reaper: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = reaction_author,
+        seeds = [
+            TWEET_REACTION_SEED.as_bytes(),
+            tweet_reaction.reactionAuthor.as_ref(),
+            tweet_reaction.parent_tweet.as_ref()
+        ],
+        bump = tweet_reaction.bump
+    )]
+    pub tweet_reaction: Account<'info, Reaction>,
+    
+    /// CHECK: receives the rent, pinned to the reaction's author
+    #[account(mut, address = tweet_reaction.reactionAuthor)]
+    pub reaction_author: UncheckedAccount<'info>,
+    
+    /// CHECK: the reaction's parent tweet, which must have been closed
+    #[account(
+        address = tweet_reaction.parent_tweet,
+        constraint = tweet.data_is_empty() @ TwitterError::TweetStillExists
+    )]
+    pub tweet: UncheckedAccount<'info>,
+}
@@ -0,0 +1,50 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the reap retweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Anyone can reap a retweet or quote tweet once its original tweet account
+///   is gone
+/// - Close the retweet account and return rent to the retweet author
+/// 
+/// NOTE: The original tweet's retweet counter is not touched, it was closed
+/// together with the tweet.
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn reap_orphaned_retweet(_ctx: Context<ReapRetweetContext>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReapRetweetContext<'info> {
+    pub reaper: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = retweet_author,
+        seeds = [
+            RETWEET_SEED.as_bytes(),
+            retweet.retweet_author.as_ref(),
+            retweet.original_tweet.as_ref()
+        ],
+        bump = retweet.bump
+    )]
+    pub retweet: Box<Account<'info, Retweet>>,
+    
+    /// CHECK: receives the rent, pinned to the retweet's author
+    #[account(mut, address = retweet.retweet_author)]
+    pub retweet_author: UncheckedAccount<'info>,
+    
+    /// CHECK: the original tweet, which must have been closed
+    #[account(
+        address = retweet.original_tweet,
+        constraint = tweet.data_is_empty() @ TwitterError::TweetStillExists
+    )]
+    pub tweet: UncheckedAccount<'info>,
+}
@@ -0,0 +1,47 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the reap tag index functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Anyone can reap a hashtag index once its tweet account is gone
+/// - Close the tag index account and return rent to the tweet author, who
+///   paid for it
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn reap_orphaned_tag_index(_ctx: Context<ReapTagIndexContext>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReapTagIndexContext<'info> {
+    pub reaper: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = tweet_author,
+        seeds = [
+            TAG_SEED.as_bytes(),
+            tag_index.tag_hash.as_ref(),
+            tag_index.tweet.as_ref()
+        ],
+        bump = tag_index.bump
+    )]
+    pub tag_index: Account<'info, TagIndex>,
+    
+    /// CHECK: receives the rent, pinned to the author of the indexed tweet
+    #[account(mut, address = tag_index.tweet_author)]
+    pub tweet_author: UncheckedAccount<'info>,
+    
+    /// CHECK: the indexed tweet, which must have been closed
+    #[account(
+        address = tag_index.tweet,
+        constraint = tweet.data_is_empty() @ TwitterError::TweetStillExists
+    )]
+    pub tweet: UncheckedAccount<'info>,
+}
@@ -5,18 +5,27 @@
 /// Requirements:
 /// - Close the comment account and return rent to comment author
 /// - Ensure only the comment author can remove their comment
+/// - Emit a RemoveCommentEvent
 /// 
-/// NOTE: No implementation logic is needed in the function body - this 
-/// functionality is achieved entirely through account constraints!
+/// NOTE: Closing and the author check are achieved entirely through account
+/// constraints, the function body only emits the event!
 /// 
 ///-------------------------------------------------------------------------------
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::hash;
 
+use crate::events::RemoveCommentEvent;
 use crate::states::*;
 
-pub fn remove_comment(_ctx: Context<RemoveCommentContext>) -> Result<()> {
+pub fn remove_comment(ctx: Context<RemoveCommentContext>) -> Result<()> {
+    let comment = &ctx.accounts.comment;
+    emit!(RemoveCommentEvent {
+        comment: comment.key(),
+        comment_author: comment.commentAuthor,
+        tweet: comment.parent_tweet,
+        id: comment.id,
+    });
+    
     Ok(())
 }
 
@@ -30,9 +39,8 @@ pub struct RemoveCommentContext<'info> {
         close = commentAuthor,
         seeds = [
             COMMENT_SEED.as_bytes(),
-            commentAuthor.key().as_ref(),
-            hash(comment.content.as_bytes()).to_bytes().as_ref(),
-            comment.parent_tweet.as_ref()
+            comment.parent_tweet.as_ref(),
+            comment.id.to_le_bytes().as_ref()
         ],
         bump = comment.bump,
         has_one = commentAuthor @ crate::errors::TwitterError::Unauthorized
@@ -4,14 +4,16 @@
 /// 
 /// Requirements:
 /// - Verify that the tweet reaction exists and belongs to the reaction author
-/// - Decrement the appropriate counter (likes or dislikes) on the tweet
+/// - Decrement the counter for the reaction type on the tweet
 /// - Close the tweet reaction account and return rent to reaction author
+/// - Emit a RemoveReactionEvent
 /// 
 ///-------------------------------------------------------------------------------
 
 use anchor_lang::prelude::*;
 
 use crate::errors::TwitterError;
+use crate::events::RemoveReactionEvent;
 use crate::states::*;
 
 pub fn remove_reaction(ctx: Context<RemoveReactionContext>) -> Result<()> {
@@ -23,21 +25,13 @@ pub fn remove_reaction(ctx: Context<RemoveReactionContext>) -> Result<()> {
     );
     
     // Decrement the appropriate counter on the tweet
-    let tweet = &mut ctx.accounts.tweet;
-    match reaction.reaction {
-        ReactionType::Like => {
-            if tweet.likes == 0 {
-                return err!(TwitterError::MinLikesReached);
-            }
-            tweet.likes -= 1;
-        },
-        ReactionType::Dislike => {
-            if tweet.dislikes == 0 {
-                return err!(TwitterError::MinDislikesReached);
-            }
-            tweet.dislikes -= 1;
-        }
-    }
+    ctx.accounts.tweet.remove_reaction(reaction.reaction)?;
+    
+    emit!(RemoveReactionEvent {
+        reaction_author: ctx.accounts.reaction_author.key(),
+        tweet: ctx.accounts.tweet.key(),
+        reaction: reaction.reaction,
+    });
     
     Ok(())
 }
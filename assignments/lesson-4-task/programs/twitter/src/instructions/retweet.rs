@@ -0,0 +1,74 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the retweet and quote tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Reject retweets from banned users
+/// - Validate quotes like tweet content
+/// - Initialize a retweet account with proper PDA seeds, so a user can only
+///   retweet or quote the same tweet once
+/// - Set retweet fields: author, original tweet, quote, creation time and bump
+/// - Increment the retweet counter on the original tweet
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn add_retweet(ctx: Context<RetweetContext>, quote: Option<String>) -> Result<()> {
+    if let Some(quote) = &quote {
+        Tweet::validate_content(quote)?;
+    }
+    
+    let retweet = &mut ctx.accounts.retweet;
+    retweet.retweet_author = ctx.accounts.retweet_author.key();
+    retweet.original_tweet = ctx.accounts.tweet.key();
+    retweet.quote = quote;
+    retweet.created_at = Clock::get()?.unix_timestamp;
+    retweet.bump = ctx.bumps.retweet;
+    
+    let tweet = &mut ctx.accounts.tweet;
+    tweet.retweet_count = tweet
+        .retweet_count
+        .checked_add(1)
+        .ok_or(TwitterError::MaxRetweetsReached)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RetweetContext<'info> {
+    #[account(mut)]
+    pub retweet_author: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = retweet_author,
+        space = 8 + Retweet::INIT_SPACE,
+        seeds = [
+            RETWEET_SEED.as_bytes(),
+            retweet_author.key().as_ref(),
+            tweet.key().as_ref()
+        ],
+        bump
+    )]
+    pub retweet: Box<Account<'info, Retweet>>,
+    
+    #[account(mut)]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    /// CHECK: ban record of the retweet author, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            retweet_author.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
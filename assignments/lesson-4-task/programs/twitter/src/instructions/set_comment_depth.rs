@@ -0,0 +1,34 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the set comment depth functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the moderation admin can change the max comment depth
+/// - Store the new depth in the moderation config, replies are checked
+///   against it from then on
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn update_max_comment_depth(ctx: Context<SetCommentDepthContext>, max_comment_depth: u8) -> Result<()> {
+    ctx.accounts.moderation_config.max_comment_depth = max_comment_depth;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCommentDepthContext<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MODERATION_SEED.as_bytes()],
+        bump = moderation_config.bump,
+        has_one = admin @ TwitterError::Unauthorized
+    )]
+    pub moderation_config: Account<'info, ModerationConfig>,
+}
@@ -0,0 +1,155 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the tip tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Transfer the tip from the tipper to the tweet author
+/// - Tip in lamports, or in SPL / Token-2022 tokens through transfer_checked
+///   when a mint and token accounts are passed
+/// - Send the treasury's fee in basis points to the treasury, once the
+///   treasury is initialized, tips before that carry no fee
+/// - Increment the tip counter on the tweet and emit a TipEvent
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::TwitterError;
+use crate::events::TipEvent;
+use crate::states::*;
+
+pub fn send_tip(ctx: Context<TipTweetContext>, amount: u64) -> Result<()> {
+    require!(amount > 0, TwitterError::InvalidTipAmount);
+    
+    let fee = Treasury::tip_fee(&ctx.accounts.treasury, amount)?;
+    let author_share = amount - fee;
+    
+    let mint = match &ctx.accounts.mint {
+        Some(mint) => {
+            let (Some(tipper_token_account), Some(author_token_account), Some(token_program)) = (
+                &ctx.accounts.tipper_token_account,
+                &ctx.accounts.author_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(TwitterError::MissingTokenAccounts);
+            };
+            
+            let pay = |to, amount| {
+                transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: tipper_token_account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to,
+                            authority: ctx.accounts.tipper.to_account_info(),
+                        },
+                    ),
+                    amount,
+                    mint.decimals,
+                )
+            };
+            
+            pay(author_token_account.to_account_info(), author_share)?;
+            if fee > 0 {
+                let Some(treasury_token_account) = &ctx.accounts.treasury_token_account else {
+                    return err!(TwitterError::MissingTokenAccounts);
+                };
+                pay(treasury_token_account.to_account_info(), fee)?;
+            }
+            Some(mint.key())
+        }
+        None => {
+            let pay = |to, amount| {
+                transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.tipper.to_account_info(),
+                            to,
+                        },
+                    ),
+                    amount,
+                )
+            };
+            
+            pay(ctx.accounts.tweet_author.to_account_info(), author_share)?;
+            if fee > 0 {
+                pay(ctx.accounts.treasury.to_account_info(), fee)?;
+            }
+            None
+        }
+    };
+    
+    let tweet = &mut ctx.accounts.tweet;
+    tweet.total_tips = tweet
+        .total_tips
+        .checked_add(1)
+        .ok_or(TwitterError::MaxTipsReached)?;
+    
+    emit!(TipEvent {
+        tipper: ctx.accounts.tipper.key(),
+        tweet: tweet.key(),
+        tweet_author: tweet.tweet_author,
+        mint,
+        amount,
+        fee,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TipTweetContext<'info> {
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+    
+    #[account(mut)]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    /// CHECK: receives the tip, pinned to the tweet's author
+    #[account(mut, address = tweet.tweet_author)]
+    pub tweet_author: UncheckedAccount<'info>,
+    
+    /// CHECK: treasury collecting the protocol fee, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = tipper,
+        token::token_program = token_program
+    )]
+    pub tipper_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = tweet_author,
+        token::token_program = token_program
+    )]
+    pub author_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
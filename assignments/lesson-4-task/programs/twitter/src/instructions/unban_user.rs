@@ -0,0 +1,45 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the unban user functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the moderation admin can lift a ban
+/// - Close the ban account and return rent to the admin
+/// 
+/// NOTE: No implementation logic is needed in the function body - this 
+/// functionality is achieved entirely through account constraints!
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn remove_ban(_ctx: Context<UnbanUserContext>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnbanUserContext<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [MODERATION_SEED.as_bytes()],
+        bump = moderation_config.bump,
+        has_one = admin @ TwitterError::Unauthorized
+    )]
+    pub moderation_config: Account<'info, ModerationConfig>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            BAN_SEED.as_bytes(),
+            ban.user.as_ref()
+        ],
+        bump = ban.bump
+    )]
+    pub ban: Account<'info, Ban>,
+}
@@ -0,0 +1,51 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the unbookmark tweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Remove the tweet from the owner's bookmark list, keeping the order of
+///   the other bookmarks
+/// - Shrink the list by one entry and refund the freed rent to the owner
+/// - Take the tweet's address rather than the account, so bookmarks of
+///   deleted tweets can still be removed
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn remove_bookmark(ctx: Context<UnbookmarkTweetContext>, tweet: Pubkey) -> Result<()> {
+    let bookmark_list = &mut ctx.accounts.bookmark_list;
+    
+    let position = bookmark_list
+        .tweets
+        .iter()
+        .position(|bookmark| *bookmark == tweet)
+        .ok_or(TwitterError::NotBookmarked)?;
+    bookmark_list.tweets.remove(position);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnbookmarkTweetContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            BOOKMARK_SEED.as_bytes(),
+            owner.key().as_ref()
+        ],
+        bump = bookmark_list.bump,
+        realloc = BookmarkList::space(bookmark_list.tweets.len().saturating_sub(1)),
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub bookmark_list: Account<'info, BookmarkList>,
+    
+    pub system_program: Program<'info, System>,
+}
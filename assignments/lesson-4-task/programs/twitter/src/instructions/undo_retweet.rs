@@ -0,0 +1,47 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the undo retweet functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the retweet author can undo the retweet
+/// - Close the retweet account and return rent to the retweet author
+/// - Decrement the retweet counter on the original tweet
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn remove_retweet(ctx: Context<UndoRetweetContext>) -> Result<()> {
+    let tweet = &mut ctx.accounts.tweet;
+    tweet.retweet_count = tweet
+        .retweet_count
+        .checked_sub(1)
+        .ok_or(TwitterError::MinRetweetsReached)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UndoRetweetContext<'info> {
+    #[account(mut)]
+    pub retweet_author: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = retweet_author,
+        seeds = [
+            RETWEET_SEED.as_bytes(),
+            retweet_author.key().as_ref(),
+            tweet.key().as_ref()
+        ],
+        bump = retweet.bump,
+        has_one = retweet_author @ TwitterError::Unauthorized
+    )]
+    pub retweet: Box<Account<'info, Retweet>>,
+    
+    #[account(mut)]
+    pub tweet: Box<Account<'info, Tweet>>,
+}
@@ -0,0 +1,68 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the unfollow functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Close the follow account and return rent to the follower
+/// - Decrement following on the follower and followers on the followed profile
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn unfollow_user(ctx: Context<UnfollowContext>) -> Result<()> {
+    let follower_profile = &mut ctx.accounts.follower_profile;
+    follower_profile.following_count = follower_profile
+        .following_count
+        .checked_sub(1)
+        .ok_or(TwitterError::MinFollowingReached)?;
+    
+    let followed_profile = &mut ctx.accounts.followed_profile;
+    followed_profile.follower_count = followed_profile
+        .follower_count
+        .checked_sub(1)
+        .ok_or(TwitterError::MinFollowersReached)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnfollowContext<'info> {
+    #[account(mut)]
+    pub follower: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            follower.key().as_ref()
+        ],
+        bump = follower_profile.bump
+    )]
+    pub follower_profile: Box<Account<'info, Profile>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            followed_profile.authority.as_ref()
+        ],
+        bump = followed_profile.bump
+    )]
+    pub followed_profile: Box<Account<'info, Profile>>,
+    
+    #[account(
+        mut,
+        close = follower,
+        seeds = [
+            FOLLOW_SEED.as_bytes(),
+            follower.key().as_ref(),
+            followed_profile.authority.as_ref()
+        ],
+        bump = follow.bump
+    )]
+    pub follow: Account<'info, Follow>,
+}
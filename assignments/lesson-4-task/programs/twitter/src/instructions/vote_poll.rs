@@ -0,0 +1,99 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the vote poll functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Reject votes from banned users
+/// - Reject votes on tweets that are not published yet or have expired
+/// - Reject voters that do not meet the tweet's token gate
+/// - Reject votes once the poll has ended
+/// - Reject options the poll does not have
+/// - Initialize a poll vote account with proper PDA seeds, so a user can
+///   only vote once per poll
+/// - Increment the tally of the chosen option
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn cast_vote(ctx: Context<VotePollContext>, option: u8) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.tweet.check_open(now)?;
+    ctx.accounts.tweet.check_gate(ctx.accounts.gate_token_account.as_deref().map(|account| &**account))?;
+    
+    let poll = &mut ctx.accounts.poll;
+    
+    require!(
+        now < poll.ends_at,
+        TwitterError::PollEnded
+    );
+    require!(
+        (option as usize) < poll.options.len(),
+        TwitterError::InvalidPollOption
+    );
+    
+    let votes = &mut poll.votes[option as usize];
+    *votes = votes
+        .checked_add(1)
+        .ok_or(TwitterError::MaxVotesReached)?;
+    
+    let poll_vote = &mut ctx.accounts.poll_vote;
+    poll_vote.voter = ctx.accounts.voter.key();
+    poll_vote.poll = poll.key();
+    poll_vote.option = option;
+    poll_vote.bump = ctx.bumps.poll_vote;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VotePollContext<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            POLL_SEED.as_bytes(),
+            poll.tweet.as_ref()
+        ],
+        bump = poll.bump
+    )]
+    pub poll: Box<Account<'info, Poll>>,
+    
+    #[account(address = poll.tweet)]
+    pub tweet: Box<Account<'info, Tweet>>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + PollVote::INIT_SPACE,
+        seeds = [
+            POLL_VOTE_SEED.as_bytes(),
+            poll.key().as_ref(),
+            voter.key().as_ref()
+        ],
+        bump
+    )]
+    pub poll_vote: Account<'info, PollVote>,
+    
+    /// CHECK: ban record of the voter, only initialized while they are banned
+    #[account(
+        seeds = [
+            BAN_SEED.as_bytes(),
+            voter.key().as_ref()
+        ],
+        bump,
+        constraint = ban.data_is_empty() @ TwitterError::UserBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+    
+    #[account(token::authority = voter)]
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,102 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the withdraw treasury functionality for the Twitter program
+/// 
+/// Requirements:
+/// - Only the treasury authority can withdraw collected fees
+/// - Withdraw lamports, keeping the treasury rent exempt
+/// - Withdraw tokens from a treasury owned token account when a mint and
+///   token accounts are passed, signing with the treasury PDA
+/// 
+///-------------------------------------------------------------------------------
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn withdraw_fees(ctx: Context<WithdrawTreasuryContext>, amount: u64) -> Result<()> {
+    let treasury = &ctx.accounts.treasury;
+    
+    match &ctx.accounts.mint {
+        Some(mint) => {
+            let (Some(treasury_token_account), Some(destination_token_account), Some(token_program)) = (
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.destination_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(TwitterError::MissingTokenAccounts);
+            };
+            
+            let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED.as_bytes(), &[treasury.bump]]];
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: treasury_token_account.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: destination_token_account.to_account_info(),
+                        authority: treasury.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                mint.decimals,
+            )
+        }
+        None => {
+            let treasury_info = treasury.to_account_info();
+            let rent_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+            let remaining = treasury_info
+                .lamports()
+                .checked_sub(amount)
+                .filter(|remaining| *remaining >= rent_minimum)
+                .ok_or(TwitterError::InsufficientTreasuryBalance)?;
+            
+            let authority_info = ctx.accounts.authority.to_account_info();
+            **authority_info.try_borrow_mut_lamports()? = authority_info
+                .lamports()
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            **treasury_info.try_borrow_mut_lamports()? = remaining;
+            
+            Ok(())
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryContext<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED.as_bytes()],
+        bump = treasury.bump,
+        has_one = authority @ TwitterError::Unauthorized
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
@@ -5,10 +5,28 @@
 /// TWITTER PROGRAM TASK
 /// 
 /// This is a decentralized Twitter-like program built on Solana that allows users to:
+/// - Create a profile with a unique handle, which also numbers their tweets
+/// - Follow and unfollow other users
 /// - Create tweets with topics and content
-/// - Add reactions (likes/dislikes) to tweets
-/// - Comment on tweets
+/// - Attach polls to tweets and vote on them once per user, and reclaim the
+///   rent of polls and votes once the poll has ended
+/// - Gate tweets behind holding a token, keeping their content off-chain
+/// - Edit or delete their own tweets
+/// - Schedule tweets to open later and expire, after which anyone can close them
+/// - Add reactions (likes, dislikes, love, laugh, boost, ...) to tweets
+/// - Comment on tweets and reply to other comments
+/// - Switch an existing reaction to a different type
 /// - Remove their own reactions and comments
+/// - Reclaim the rent of reactions and comments left behind by deleted tweets
+/// - Retweet or quote tweet other tweets, and reclaim the rent of retweets of
+///   closed tweets
+/// - Bookmark tweets into a personal list
+/// - Tag tweets with #hashtags and @mentions, indexed in their own accounts,
+///   and reclaim the rent of hashtag indexes left behind by closed tweets
+/// - Hide comments on their own tweets
+/// - A moderation admin can ban users from tweeting, reacting and commenting
+///   and configure how deep replies may nest
+/// - Tip tweet authors in SOL or tokens, with an optional protocol fee
 /// 
 /// INSTRUCTIONS:
 /// Complete the implementation of all instructions by filling in the TODOs.
@@ -23,9 +41,23 @@
 /// - Imports
 /// 
 /// SEEDS:
-/// - Tweet: [topic.as_bytes(), TWEET_SEED.as_bytes(), tweet_authority.key().as_ref()]
+/// - Profile: [PROFILE_SEED.as_bytes(), authority.key().as_ref()]
+/// - HandleRegistry: [HANDLE_SEED.as_bytes(), handle.as_bytes()]
+/// - Follow: [FOLLOW_SEED.as_bytes(), follower.key().as_ref(), followed_profile.authority.as_ref()]
+/// - Tweet: [TWEET_SEED.as_bytes(), tweet_authority.key().as_ref(), profile.tweet_count.to_le_bytes().as_ref()]
+/// - LegacyTweet (migrate_tweet only): [topic.as_bytes(), TWEET_SEED.as_bytes(), tweet_authority.key().as_ref()]
+/// - LegacyComment (reap_legacy_comment only): [COMMENT_SEED.as_bytes(), comment_author.key().as_ref(), hash(content).as_ref(), tweet.key().as_ref()]
 /// - TweetReaction: [TWEET_REACTION_SEED.as_bytes(), reaction_author.key().as_ref(), tweet.key().as_ref()]
-/// - Comment: [COMMENT_SEED.as_bytes(), comment_author.key().as_ref(), {hash(comment.content.as_bytes()).to_bytes().as_ref()}, comment.parent_tweet.key().as_ref()]
+/// - Poll: [POLL_SEED.as_bytes(), tweet.key().as_ref()]
+/// - PollVote: [POLL_VOTE_SEED.as_bytes(), poll.key().as_ref(), voter.key().as_ref()]
+/// - Retweet: [RETWEET_SEED.as_bytes(), retweet_author.key().as_ref(), tweet.key().as_ref()]
+/// - BookmarkList: [BOOKMARK_SEED.as_bytes(), owner.key().as_ref()]
+/// - TagIndex: [TAG_SEED.as_bytes(), hash(tag).as_ref(), tweet.key().as_ref()]
+/// - Mention: [MENTION_SEED.as_bytes(), profile.key().as_ref(), tweet.key().as_ref()]
+/// - ModerationConfig: [MODERATION_SEED.as_bytes()]
+/// - Ban: [BAN_SEED.as_bytes(), user.key().as_ref()]
+/// - Treasury: [TREASURY_SEED.as_bytes()]
+/// - Comment: [COMMENT_SEED.as_bytes(), tweet.key().as_ref(), tweet.comment_count.to_le_bytes().as_ref()]
 /// 
 /// GOOD LUCK!
 /// 
@@ -35,6 +67,7 @@ use crate::instructions::*;
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod states;
 
@@ -45,8 +78,39 @@ pub mod twitter {
 
     use super::*;
 
-    pub fn initialize(ctx: Context<InitializeTweet>, topic: String, content: String) -> Result<()> {
-        initialize_tweet(ctx, topic, content)
+    pub fn create_profile(
+        ctx: Context<CreateProfileContext>,
+        handle: String,
+        display_name: String,
+        bio: String,
+    ) -> Result<()> {
+        initialize_profile(ctx, handle, display_name, bio)
+    }
+    pub fn follow(ctx: Context<FollowContext>) -> Result<()> {
+        follow_user(ctx)
+    }
+    pub fn unfollow(ctx: Context<UnfollowContext>) -> Result<()> {
+        unfollow_user(ctx)
+    }
+    pub fn initialize<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeTweet<'info>>,
+        topic: String,
+        content: String,
+        options: TweetOptions,
+    ) -> Result<()> {
+        initialize_tweet(ctx, topic, content, options)
+    }
+    pub fn edit_tweet(ctx: Context<EditTweetContext>, content: String) -> Result<()> {
+        update_tweet(ctx, content)
+    }
+    pub fn delete_tweet(ctx: Context<DeleteTweetContext>) -> Result<()> {
+        remove_tweet(ctx)
+    }
+    pub fn expire_tweet(ctx: Context<ExpireTweetContext>) -> Result<()> {
+        close_expired_tweet(ctx)
+    }
+    pub fn migrate_tweet(ctx: Context<MigrateTweetContext>, topic: String) -> Result<()> {
+        migrate_legacy_tweet(ctx, topic)
     }
     pub fn like_tweet(ctx: Context<AddReactionContext>) -> Result<()> {
         add_reaction(ctx, states::ReactionType::Like)
@@ -54,13 +118,88 @@ pub mod twitter {
     pub fn dislike_tweet(ctx: Context<AddReactionContext>) -> Result<()> {
         add_reaction(ctx, states::ReactionType::Dislike)
     }
+    pub fn react_tweet(ctx: Context<AddReactionContext>, reaction: states::ReactionType) -> Result<()> {
+        add_reaction(ctx, reaction)
+    }
     pub fn reaction_remove(ctx: Context<RemoveReactionContext>) -> Result<()> {
         remove_reaction(ctx)
     }
+    pub fn change_reaction(ctx: Context<ChangeReactionContext>, reaction: states::ReactionType) -> Result<()> {
+        update_reaction(ctx, reaction)
+    }
     pub fn comment_tweet(ctx: Context<AddCommentContext>, comment_content: String) -> Result<()> {
         add_comment(ctx, comment_content)
     }
     pub fn comment_remove(ctx: Context<RemoveCommentContext>) -> Result<()> {
         remove_comment(ctx)
     }
+    pub fn vote_poll(ctx: Context<VotePollContext>, option: u8) -> Result<()> {
+        cast_vote(ctx, option)
+    }
+    pub fn close_poll(ctx: Context<ClosePollContext>) -> Result<()> {
+        close_finished_poll(ctx)
+    }
+    pub fn reap_poll_vote(ctx: Context<ReapPollVoteContext>) -> Result<()> {
+        reap_finished_poll_vote(ctx)
+    }
+    pub fn reap_reaction(ctx: Context<ReapReactionContext>) -> Result<()> {
+        reap_orphaned_reaction(ctx)
+    }
+    pub fn reap_comment(ctx: Context<ReapCommentContext>) -> Result<()> {
+        reap_orphaned_comment(ctx)
+    }
+    pub fn reap_legacy_comment(ctx: Context<ReapLegacyCommentContext>, content: String) -> Result<()> {
+        reap_orphaned_legacy_comment(ctx, content)
+    }
+    pub fn retweet(ctx: Context<RetweetContext>) -> Result<()> {
+        add_retweet(ctx, None)
+    }
+    pub fn quote_tweet(ctx: Context<RetweetContext>, content: String) -> Result<()> {
+        add_retweet(ctx, Some(content))
+    }
+    pub fn undo_retweet(ctx: Context<UndoRetweetContext>) -> Result<()> {
+        remove_retweet(ctx)
+    }
+    pub fn reap_retweet(ctx: Context<ReapRetweetContext>) -> Result<()> {
+        reap_orphaned_retweet(ctx)
+    }
+    pub fn create_bookmark_list(ctx: Context<CreateBookmarkListContext>) -> Result<()> {
+        initialize_bookmark_list(ctx)
+    }
+    pub fn bookmark_tweet(ctx: Context<BookmarkTweetContext>) -> Result<()> {
+        add_bookmark(ctx)
+    }
+    pub fn unbookmark_tweet(ctx: Context<UnbookmarkTweetContext>, tweet: Pubkey) -> Result<()> {
+        remove_bookmark(ctx, tweet)
+    }
+    pub fn close_mention(ctx: Context<CloseMentionContext>) -> Result<()> {
+        dismiss_mention(ctx)
+    }
+    pub fn reap_tag_index(ctx: Context<ReapTagIndexContext>) -> Result<()> {
+        reap_orphaned_tag_index(ctx)
+    }
+    pub fn hide_comment(ctx: Context<HideCommentContext>, hidden: bool) -> Result<()> {
+        set_comment_hidden(ctx, hidden)
+    }
+    pub fn initialize_moderation(ctx: Context<InitializeModerationContext>, admin: Pubkey) -> Result<()> {
+        create_moderation_config(ctx, admin)
+    }
+    pub fn set_comment_depth(ctx: Context<SetCommentDepthContext>, max_comment_depth: u8) -> Result<()> {
+        update_max_comment_depth(ctx, max_comment_depth)
+    }
+    pub fn ban_user(ctx: Context<BanUserContext>) -> Result<()> {
+        add_ban(ctx)
+    }
+    pub fn unban_user(ctx: Context<UnbanUserContext>) -> Result<()> {
+        remove_ban(ctx)
+    }
+    pub fn initialize_treasury(ctx: Context<InitializeTreasuryContext>, fee_bps: u16) -> Result<()> {
+        create_treasury(ctx, fee_bps)
+    }
+    pub fn tip_tweet(ctx: Context<TipTweetContext>, amount: u64) -> Result<()> {
+        send_tip(ctx, amount)
+    }
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasuryContext>, amount: u64) -> Result<()> {
+        withdraw_fees(ctx, amount)
+    }
 }
@@ -1,36 +1,441 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use unicode_normalization::UnicodeNormalization;
 
+use crate::errors::TwitterError;
+
+// Text limits are in characters, with a byte cap on top that sizes the accounts
 pub const TOPIC_LENGTH: usize = 32;
 pub const CONTENT_LENGTH: usize = 500;
 pub const COMMENT_LENGTH: usize = 500;
+pub const TOPIC_MAX_BYTES: usize = 64;
+pub const CONTENT_MAX_BYTES: usize = 1000;
+pub const COMMENT_MAX_BYTES: usize = 1000;
+pub const MAX_COMMENT_DEPTH: u8 = 8; // until the moderation admin configures another one
+pub const HANDLE_LENGTH: usize = 16;
+pub const DISPLAY_NAME_LENGTH: usize = 50;
+pub const BIO_LENGTH: usize = 160;
+pub const MAX_BOOKMARKS: usize = 256;
+pub const MIN_POLL_OPTIONS: usize = 2;
+pub const MAX_POLL_OPTIONS: usize = 4;
+pub const POLL_OPTION_LENGTH: usize = 50;
+pub const POLL_OPTION_MAX_BYTES: usize = 100;
+pub const MAX_TAGS: usize = 5; // hashtags and mentions per tweet
 
 pub const TWEET_SEED: &str = "TWEET_SEED";
 pub const TWEET_REACTION_SEED: &str = "TWEET_REACTION_SEED";
 pub const COMMENT_SEED: &str = "COMMENT_SEED";
+pub const PROFILE_SEED: &str = "PROFILE_SEED";
+pub const HANDLE_SEED: &str = "HANDLE_SEED";
+pub const FOLLOW_SEED: &str = "FOLLOW_SEED";
+pub const MODERATION_SEED: &str = "MODERATION_SEED";
+pub const BAN_SEED: &str = "BAN_SEED";
+pub const TREASURY_SEED: &str = "TREASURY_SEED";
+pub const RETWEET_SEED: &str = "RETWEET_SEED";
+pub const BOOKMARK_SEED: &str = "BOOKMARK_SEED";
+pub const POLL_SEED: &str = "POLL_SEED";
+pub const POLL_VOTE_SEED: &str = "POLL_VOTE_SEED";
+pub const TAG_SEED: &str = "TAG_SEED";
+pub const MENTION_SEED: &str = "MENTION_SEED";
+
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Checks a user supplied text is not blank, has at most `max_chars`
+/// characters and fits the `max_bytes` its account has room for
+fn validate_text(
+    text: &str,
+    max_chars: usize,
+    max_bytes: usize,
+    empty: TwitterError,
+    too_long: TwitterError,
+) -> Result<()> {
+    if text.trim().is_empty() {
+        return Err(empty.into());
+    }
+    if text.chars().count() > max_chars || text.len() > max_bytes {
+        return Err(too_long.into());
+    }
+    Ok(())
+}
 
-#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+/// A `#hashtag` or `@handle` token of a tweet's content, NFC normalized and lowercased
+#[derive(Clone, PartialEq, Debug)]
+pub enum Tag {
+    Hashtag(String),
+    Mention(String),
+}
+
+impl Tag {
+    /// Distinct hashtags and mentions of `content`, in order of appearance.
+    ///
+    /// A tag runs from the `#` or `@` at the start of a word up to the first
+    /// character that is not alphanumeric or `_`. Mentions only keep tokens
+    /// that can be handles. The content is NFC normalized first, so composed
+    /// and decomposed spellings of a hashtag index under the same hash.
+    pub fn parse(content: &str) -> Vec<Tag> {
+        let content: String = content.nfc().collect();
+        let mut tags = Vec::new();
+        for word in content.split_whitespace() {
+            let mut chars = word.chars();
+            let tag = match chars.next() {
+                Some('#') => {
+                    let tag: String = chars
+                        .take_while(|c| c.is_alphanumeric() || *c == '_')
+                        .flat_map(char::to_lowercase)
+                        .collect();
+                    (!tag.is_empty()).then_some(Tag::Hashtag(tag))
+                }
+                Some('@') => {
+                    let handle: String = chars
+                        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                        .map(|c| c.to_ascii_lowercase())
+                        .collect();
+                    (!handle.is_empty() && handle.len() <= HANDLE_LENGTH).then_some(Tag::Mention(handle))
+                }
+                _ => None,
+            };
+            if let Some(tag) = tag {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        tags
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq)]
 pub enum ReactionType {
     Like,
     Dislike,
+    Love,
+    Laugh,
+    Boost,
+    Wow,
+    Sad,
+    Angry,
 }
 
 impl ReactionType {
     pub const SPACE: usize = 1; // 1 byte for enum discriminant
+    pub const COUNT: usize = 8; // number of variants, one counter each on Tweet
+
+    /// Position of this reaction's counter in `Tweet::reaction_counts`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub authority: Pubkey,
+    #[max_len(HANDLE_LENGTH)]
+    pub handle: String,
+    #[max_len(DISPLAY_NAME_LENGTH)]
+    pub display_name: String,
+    #[max_len(BIO_LENGTH)]
+    pub bio: String,
+    pub tweet_count: u64, // index of the next tweet, never reused after a delete
+    pub follower_count: u64,
+    pub following_count: u64,
+    pub bump: u8,
+}
+
+/// Claims a handle for one profile, its address is derived from the handle
+#[account]
+#[derive(InitSpace)]
+pub struct HandleRegistry {
+    pub profile: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ModerationConfig {
+    pub admin: Pubkey,
+    pub max_comment_depth: u8,
+    pub bump: u8,
+}
+
+impl ModerationConfig {
+    /// Deepest reply level allowed, read from the moderation config once it exists
+    pub fn max_comment_depth(config: &AccountInfo) -> Result<u8> {
+        if config.data_is_empty() {
+            return Ok(MAX_COMMENT_DEPTH);
+        }
+        let config = ModerationConfig::try_deserialize(&mut &config.try_borrow_data()?[..])?;
+        Ok(config.max_comment_depth)
+    }
+}
+
+/// Collects the protocol fee on tips, in lamports or in token accounts it owns
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub authority: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl Treasury {
+    /// Protocol share of a tip of `amount`, rounded down, read from the treasury once it exists
+    pub fn tip_fee(treasury: &AccountInfo, amount: u64) -> Result<u64> {
+        if treasury.data_is_empty() {
+            return Ok(0);
+        }
+        let treasury = Treasury::try_deserialize(&mut &treasury.try_borrow_data()?[..])?;
+        let fee = (amount as u128)
+            .checked_mul(treasury.fee_bps as u128)
+            .and_then(|fee| fee.checked_div(MAX_FEE_BPS as u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(fee as u64)
+    }
+}
+
+/// Exists while `user` is banned, instructions check its address is empty
+#[account]
+#[derive(InitSpace)]
+pub struct Ban {
+    pub user: Pubkey,
+    pub banned_by: Pubkey,
+    pub banned_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Follow {
+    pub follower: Pubkey,
+    pub followed: Pubkey,
+    pub bump: u8,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct Tweet {
     pub tweet_author: Pubkey,
-    #[max_len(TOPIC_LENGTH)]
+    #[max_len(TOPIC_MAX_BYTES)]
+    pub topic: String, // NFC normalized
+    #[max_len(CONTENT_MAX_BYTES)]
+    pub content: String,
+    pub reaction_counts: [u64; ReactionType::COUNT],
+    pub edit_count: u32,
+    pub last_edited_at: i64, // 0 until the first edit
+    pub previous_content_hash: [u8; 32], // hash of the content replaced by the last edit
+    pub index: u64, // position in the author's Profile::tweet_count sequence
+    pub comment_count: u64, // id of the next comment, never reused after a removal
+    pub total_tips: u64, // number of tips, amounts are in TipEvent as they may be in different mints
+    pub retweet_count: u64, // retweets and quote tweets
+    pub content_hash: [u8; 32], // hash of the full content, kept off-chain (e.g. encrypted) for gated tweets
+    pub gate_mint: Option<Pubkey>, // reacting and commenting need min_balance of this token
+    pub min_balance: u64,
+    pub publish_at: i64, // reactions and comments are accepted from this time
+    pub expires_at: i64, // 0 if the tweet never expires
+    pub bump: u8,
+}
+
+impl Tweet {
+    /// Offset of `topic` in the account data, for memcmp filters by topic
+    pub const TOPIC_OFFSET: usize = 8 + 32;
+
+    /// NFC normalizes a topic and checks it against the topic limits, so the
+    /// same topic typed with composed or decomposed accents matches in filters
+    pub fn normalize_topic(topic: &str) -> Result<String> {
+        let topic: String = topic.nfc().collect();
+        validate_text(&topic, TOPIC_LENGTH, TOPIC_MAX_BYTES, TwitterError::TopicEmpty, TwitterError::TopicTooLong)?;
+        Ok(topic)
+    }
+
+    pub fn validate_content(content: &str) -> Result<()> {
+        validate_text(content, CONTENT_LENGTH, CONTENT_MAX_BYTES, TwitterError::ContentEmpty, TwitterError::ContentTooLong)
+    }
+
+    /// Checks the signer's token account meets the tweet's token gate, if it has one
+    pub fn check_gate(&self, token_account: Option<&TokenAccount>) -> Result<()> {
+        let Some(gate_mint) = self.gate_mint else {
+            return Ok(());
+        };
+        match token_account {
+            Some(account) if account.mint == gate_mint && account.amount >= self.min_balance => Ok(()),
+            _ => err!(TwitterError::TokenGateNotMet),
+        }
+    }
+
+    /// Checks the tweet is published and not yet expired at `now`
+    pub fn check_open(&self, now: i64) -> Result<()> {
+        require!(now >= self.publish_at, TwitterError::TweetNotPublished);
+        require!(!self.is_expired(now), TwitterError::TweetExpired);
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    pub fn reaction_count(&self, reaction: ReactionType) -> u64 {
+        self.reaction_counts[reaction.index()]
+    }
+
+    pub fn likes(&self) -> u64 {
+        self.reaction_count(ReactionType::Like)
+    }
+
+    pub fn dislikes(&self) -> u64 {
+        self.reaction_count(ReactionType::Dislike)
+    }
+
+    pub fn add_reaction(&mut self, reaction: ReactionType) -> Result<()> {
+        let count = &mut self.reaction_counts[reaction.index()];
+        *count = count.checked_add(1).ok_or(match reaction {
+            ReactionType::Like => TwitterError::MaxLikesReached,
+            ReactionType::Dislike => TwitterError::MaxDislikesReached,
+            _ => TwitterError::MaxReactionsReached,
+        })?;
+        Ok(())
+    }
+
+    pub fn remove_reaction(&mut self, reaction: ReactionType) -> Result<()> {
+        let count = &mut self.reaction_counts[reaction.index()];
+        *count = count.checked_sub(1).ok_or(match reaction {
+            ReactionType::Like => TwitterError::MinLikesReached,
+            ReactionType::Dislike => TwitterError::MinDislikesReached,
+            _ => TwitterError::MinReactionsReached,
+        })?;
+        Ok(())
+    }
+}
+
+/// A retweet of `original_tweet`, or a quote tweet when it carries its own content
+#[account]
+#[derive(InitSpace)]
+pub struct Retweet {
+    pub retweet_author: Pubkey,
+    pub original_tweet: Pubkey,
+    #[max_len(CONTENT_MAX_BYTES)]
+    pub quote: Option<String>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Poll attached to `tweet` when it is created, votes are accepted until `ends_at`
+#[account]
+#[derive(InitSpace)]
+pub struct Poll {
+    pub tweet: Pubkey,
+    pub author: Pubkey, // paid the rent, gets it back when the poll is closed
+    #[max_len(MAX_POLL_OPTIONS, POLL_OPTION_MAX_BYTES)]
+    pub options: Vec<String>,
+    pub votes: [u64; MAX_POLL_OPTIONS], // tally per option, unused options stay 0
+    pub ends_at: i64,
+    pub bump: u8,
+}
+
+impl Poll {
+    pub fn validate_options(options: &[String]) -> Result<()> {
+        require!(
+            (MIN_POLL_OPTIONS..=MAX_POLL_OPTIONS).contains(&options.len()),
+            TwitterError::InvalidPollOptionCount
+        );
+        for option in options {
+            validate_text(
+                option,
+                POLL_OPTION_LENGTH,
+                POLL_OPTION_MAX_BYTES,
+                TwitterError::PollOptionEmpty,
+                TwitterError::PollOptionTooLong,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// One per (poll, voter), so each user votes once
+#[account]
+#[derive(InitSpace)]
+pub struct PollVote {
+    pub voter: Pubkey,
+    pub poll: Pubkey,
+    pub option: u8,
+    pub bump: u8,
+}
+
+/// Points a hashtag at a tweet using it, clients find a tag's tweets with a
+/// memcmp filter on `tag_hash`, the hash of the lowercased tag. Anyone can
+/// reap it once the tweet is gone
+#[account]
+#[derive(InitSpace)]
+pub struct TagIndex {
+    pub tag_hash: [u8; 32],
+    pub tweet: Pubkey,
+    pub tweet_author: Pubkey, // paid the rent, gets it back when the index is reaped
+    pub bump: u8,
+}
+
+/// Notifies `profile` it was mentioned in `tweet`, until its owner closes it
+#[account]
+#[derive(InitSpace)]
+pub struct Mention {
+    pub profile: Pubkey,
+    pub tweet: Pubkey,
+    pub tweet_author: Pubkey, // paid the rent, gets it back when the mention is closed
+    pub bump: u8,
+}
+
+/// Tweets saved by `owner`, the account grows and shrinks with the list
+#[account]
+pub struct BookmarkList {
+    pub owner: Pubkey,
+    pub tweets: Vec<Pubkey>, // at most MAX_BOOKMARKS, oldest first
+    pub bump: u8,
+}
+
+impl BookmarkList {
+    /// Account size holding `len` bookmarks
+    pub fn space(len: usize) -> usize {
+        8 + // discriminator
+        32 + // owner: Pubkey
+        4 + 32 * len + // tweets: Vec<Pubkey> (4 bytes for length + 32 per tweet)
+        1 // bump: u8
+    }
+}
+
+/// Tweet layout from before tweets were seeded by profile index, kept so
+/// `migrate_tweet` can read accounts created at `[topic, TWEET_SEED, author]`
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LegacyTweet {
+    pub tweet_author: Pubkey,
     pub topic: String,
-    #[max_len(CONTENT_LENGTH)]
     pub content: String,
     pub likes: u64,
     pub dislikes: u64,
     pub bump: u8,
 }
 
+/// Comment layout from before comments had ids and replies, kept so
+/// `reap_legacy_comment` can read accounts created at
+/// `[COMMENT_SEED, author, hash(content), tweet]`
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LegacyComment {
+    pub commentAuthor: Pubkey,
+    pub parent_tweet: Pubkey,
+    pub content: String,
+    pub bump: u8,
+}
+
+/// Closes an account the handler decoded by hand, the same way
+/// `close = recipient` would for a typed account
+pub fn close_legacy_account(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&System::id());
+    account.resize(0)?;
+    Ok(())
+}
+
 #[account]
 pub struct Reaction {
     pub reactionAuthor: Pubkey,
@@ -51,7 +456,11 @@ impl Reaction {
 pub struct Comment {
     pub commentAuthor: Pubkey,
     pub parent_tweet: Pubkey,
+    pub id: u64,
     pub content: String,
+    pub parent_comment: Option<Pubkey>,
+    pub depth: u8,
+    pub hidden: bool, // set by the tweet author, clients should not render it
     pub bump: u8,
 }
 
@@ -59,6 +468,14 @@ impl Comment {
     pub const INIT_SPACE: usize = 8 + // discriminator
         32 + // commentAuthor: Pubkey
         32 + // parent_tweet: Pubkey
-        4 + COMMENT_LENGTH + // content: String (4 bytes for length + max content length)
+        8 + // id: u64
+        4 + COMMENT_MAX_BYTES + // content: String (4 bytes for length + max content length)
+        1 + 32 + // parent_comment: Option<Pubkey>
+        1 + // depth: u8
+        1 + // hidden: bool
         1; // bump: u8
+
+    pub fn validate_content(content: &str) -> Result<()> {
+        validate_text(content, COMMENT_LENGTH, COMMENT_MAX_BYTES, TwitterError::CommentEmpty, TwitterError::CommentTooLong)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,293 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorSerialize, Discriminator, Space};
+use solana_sdk::signature::{Keypair, Signer};
+use twitter::errors::TwitterError;
+use twitter::events::{AddCommentEvent, RemoveCommentEvent};
+use twitter::states::*;
+
+use common::*;
+
+fn setup() -> (TwitterTest, Keypair, Keypair, Pubkey) {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut test = TwitterTest::new(&[&alice, &bob]);
+    test.create_profile(&alice, "alice");
+    let tweet = test.tweet(&alice, "Hello", "Comment on this");
+    (test, alice, bob, tweet)
+}
+
+#[test]
+fn comment_tweet_stores_comment_and_emits_event() {
+    let (mut test, _, bob, tweet) = setup();
+    let comment = comment_pda(&tweet, 0);
+
+    let outcome = test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "Nice tweet"), &[&bob]);
+    outcome.assert_ok();
+
+    let state = test.account::<Comment>(&comment);
+    assert_eq!(state.commentAuthor, bob.pubkey());
+    assert_eq!(state.parent_tweet, tweet);
+    assert_eq!(state.id, 0);
+    assert_eq!(state.content, "Nice tweet");
+    assert_eq!(state.parent_comment, None);
+    assert_eq!(state.depth, 0);
+    assert_eq!(test.account::<Tweet>(&tweet).comment_count, 1);
+
+    let events = outcome.events::<AddCommentEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].comment, comment);
+    assert_eq!(events[0].comment_author, bob.pubkey());
+    assert_eq!(events[0].id, 0);
+}
+
+#[test]
+fn comment_tweet_past_the_maximum_fails() {
+    let (mut test, alice, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    let mut state = tweet_state(&alice.pubkey(), 1, [0; ReactionType::COUNT]);
+    state.comment_count = u64::MAX;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+
+    let outcome = test
+        .send(comment_ix(&bob.pubkey(), &tweet, u64::MAX, None, "One too many"), &[&bob]);
+
+    outcome.assert_error(TwitterError::MaxCommentsReached);
+}
+
+#[test]
+fn comment_tweet_before_publish_time_fails() {
+    let (mut test, alice, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    let mut state = tweet_state(&alice.pubkey(), 1, [0; ReactionType::COUNT]);
+    state.publish_at = i64::MAX;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+
+    let outcome = test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "Too early"), &[&bob]);
+
+    outcome.assert_error(TwitterError::TweetNotPublished);
+}
+
+#[test]
+fn comment_tweet_allows_the_same_text_twice() {
+    let (mut test, _, bob, tweet) = setup();
+
+    for id in 0..2 {
+        test.send(comment_ix(&bob.pubkey(), &tweet, id, None, "Same text"), &[&bob]).assert_ok();
+    }
+
+    assert_eq!(test.account::<Comment>(&comment_pda(&tweet, 1)).id, 1);
+    assert_eq!(test.account::<Tweet>(&tweet).comment_count, 2);
+}
+
+#[test]
+fn comment_tweet_rejects_comment_too_long() {
+    let (mut test, _, bob, tweet) = setup();
+    let content = "C".repeat(COMMENT_LENGTH + 1);
+
+    let outcome = test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, &content), &[&bob]);
+
+    outcome.assert_error(TwitterError::CommentTooLong);
+    assert_eq!(test.account::<Tweet>(&tweet).comment_count, 0);
+}
+
+#[test]
+fn comment_tweet_rejects_comment_over_the_byte_cap() {
+    let (mut test, _, bob, tweet) = setup();
+    let content = "💯".repeat(COMMENT_MAX_BYTES / 4 + 1);
+    assert!(content.chars().count() <= COMMENT_LENGTH);
+
+    let outcome = test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, &content), &[&bob]);
+
+    outcome.assert_error(TwitterError::CommentTooLong);
+}
+
+#[test]
+fn comment_tweet_rejects_blank_comment() {
+    let (mut test, _, bob, tweet) = setup();
+
+    let outcome = test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "  "), &[&bob]);
+
+    outcome.assert_error(TwitterError::CommentEmpty);
+}
+
+#[test]
+fn comment_tweet_rejects_banned_user() {
+    let (mut test, _, bob, tweet) = setup();
+    let ban = Ban {
+        user: bob.pubkey(),
+        banned_by: Pubkey::new_unique(),
+        banned_at: 0,
+        bump: 0,
+    };
+    test.set_account(&ban_pda(&bob.pubkey()), &ban, 8 + Ban::INIT_SPACE);
+
+    let outcome = test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "Let me in"), &[&bob]);
+
+    outcome.assert_error(TwitterError::UserBanned);
+}
+
+#[test]
+fn reply_to_a_comment_of_another_tweet_fails() {
+    let (mut test, alice, bob, tweet) = setup();
+    let other_tweet = test.tweet(&alice, "Other", "Another tweet");
+    test.send(comment_ix(&bob.pubkey(), &other_tweet, 0, None, "Elsewhere"), &[&bob]).assert_ok();
+
+    let outcome = test.send(
+            comment_ix(&bob.pubkey(), &tweet, 0, Some(comment_pda(&other_tweet, 0)), "Reply"),
+            &[&bob],
+        );
+
+    outcome.assert_error(TwitterError::ParentCommentMismatch);
+}
+
+#[test]
+fn reply_below_the_maximum_depth_fails() {
+    let (mut test, _, bob, tweet) = setup();
+    // A comment at the deepest level, replies to it would be one level too deep
+    let parent = Pubkey::new_unique();
+    let state = Comment {
+        commentAuthor: bob.pubkey(),
+        parent_tweet: tweet,
+        id: 0,
+        content: "Deep".to_string(),
+        parent_comment: None,
+        depth: MAX_COMMENT_DEPTH,
+        hidden: false,
+        bump: 0,
+    };
+    test.set_account(&parent, &state, Comment::INIT_SPACE);
+
+    let outcome = test
+        .send(comment_ix(&bob.pubkey(), &tweet, 0, Some(parent), "Too deep"), &[&bob]);
+
+    outcome.assert_error(TwitterError::MaxCommentDepthReached);
+}
+
+/// Injects the moderation config, `initialize_moderation` needs the program's upgrade authority
+fn inject_moderation(test: &mut TwitterTest, admin: &Pubkey, max_comment_depth: u8) {
+    let (_, bump) = Pubkey::find_program_address(&[MODERATION_SEED.as_bytes()], &twitter::ID);
+    let config = ModerationConfig {
+        admin: *admin,
+        max_comment_depth,
+        bump,
+    };
+    test.set_account(&moderation_pda(), &config, 8 + ModerationConfig::INIT_SPACE);
+}
+
+#[test]
+fn reply_below_the_configured_depth_fails() {
+    let (mut test, alice, bob, tweet) = setup();
+    inject_moderation(&mut test, &alice.pubkey(), 1);
+    test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "Top"), &[&bob]).assert_ok();
+    test.send(comment_ix(&bob.pubkey(), &tweet, 1, Some(comment_pda(&tweet, 0)), "Reply"), &[&bob])
+        .assert_ok();
+
+    let outcome = test
+        .send(comment_ix(&bob.pubkey(), &tweet, 2, Some(comment_pda(&tweet, 1)), "Too deep"), &[&bob]);
+
+    outcome.assert_error(TwitterError::MaxCommentDepthReached);
+}
+
+#[test]
+fn set_comment_depth_is_admin_only() {
+    let (mut test, alice, bob, _) = setup();
+    inject_moderation(&mut test, &alice.pubkey(), MAX_COMMENT_DEPTH);
+
+    test.send(set_comment_depth_ix(&bob.pubkey(), 2), &[&bob])
+        .assert_error(TwitterError::Unauthorized);
+    test.send(set_comment_depth_ix(&alice.pubkey(), 2), &[&alice]).assert_ok();
+
+    let config = test.account::<ModerationConfig>(&moderation_pda());
+    assert_eq!(config.max_comment_depth, 2);
+}
+
+#[test]
+fn comment_remove_closes_comment_and_refunds_rent() {
+    let (mut test, _, bob, tweet) = setup();
+    test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "Short lived"), &[&bob]).assert_ok();
+
+    let comment = comment_pda(&tweet, 0);
+    let comment_lamports = test.balance(&comment);
+    let bob_before = test.balance(&bob.pubkey());
+
+    let outcome = test.send(comment_remove_ix(&bob.pubkey(), &comment), &[&bob]);
+    outcome.assert_ok();
+
+    assert!(!test.exists(&comment));
+    assert_eq!(test.balance(&bob.pubkey()), bob_before + comment_lamports);
+
+    let events = outcome.events::<RemoveCommentEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].comment, comment);
+    assert_eq!(events[0].comment_author, bob.pubkey());
+}
+
+#[test]
+fn comment_remove_by_another_user_fails() {
+    let (mut test, alice, bob, tweet) = setup();
+    test.send(comment_ix(&bob.pubkey(), &tweet, 0, None, "Mine"), &[&bob]).assert_ok();
+
+    let comment = comment_pda(&tweet, 0);
+    let outcome = test.send(comment_remove_ix(&alice.pubkey(), &comment), &[&alice]);
+
+    outcome.assert_error(TwitterError::Unauthorized);
+    assert!(test.exists(&comment));
+}
+
+/// Account data of a comment created before comments had ids and replies
+fn legacy_comment_data(author: &Pubkey, content: &str, tweet: &Pubkey) -> Vec<u8> {
+    let legacy = LegacyComment {
+        commentAuthor: *author,
+        parent_tweet: *tweet,
+        content: content.to_string(),
+        bump: 0,
+    };
+    let mut data = Comment::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn reap_legacy_comment_refunds_author_once_tweet_is_gone() {
+    let (mut test, alice, bob, _) = setup();
+    // Stands in for a legacy tweet that was migrated or deleted
+    let gone = legacy_tweet_pda("Old topic", &alice.pubkey());
+    let comment = legacy_comment_pda(&bob.pubkey(), "Old comment", &gone);
+    test.set_raw_account(&comment, legacy_comment_data(&bob.pubkey(), "Old comment", &gone), 200);
+
+    let comment_lamports = test.balance(&comment);
+    let bob_before = test.balance(&bob.pubkey());
+    test.send(reap_legacy_comment_ix(&alice.pubkey(), &bob.pubkey(), "Old comment", &gone), &[&alice])
+        .assert_ok();
+
+    assert!(!test.exists(&comment));
+    assert_eq!(test.balance(&bob.pubkey()), bob_before + comment_lamports);
+}
+
+#[test]
+fn reap_legacy_comment_rejects_while_tweet_exists() {
+    let (mut test, alice, bob, tweet) = setup();
+    let comment = legacy_comment_pda(&bob.pubkey(), "Old comment", &tweet);
+    test.set_raw_account(&comment, legacy_comment_data(&bob.pubkey(), "Old comment", &tweet), 200);
+
+    let outcome = test.send(reap_legacy_comment_ix(&alice.pubkey(), &bob.pubkey(), "Old comment", &tweet), &[&alice]);
+
+    outcome.assert_error(TwitterError::TweetStillExists);
+    assert!(test.exists(&comment));
+}
+
+#[test]
+fn reap_legacy_comment_rejects_comment_of_another_author() {
+    let (mut test, alice, bob, _) = setup();
+    let gone = legacy_tweet_pda("Old topic", &alice.pubkey());
+    // Stored at bob's legacy address but claiming a different author
+    let comment = legacy_comment_pda(&bob.pubkey(), "Old comment", &gone);
+    test.set_raw_account(&comment, legacy_comment_data(&Pubkey::new_unique(), "Old comment", &gone), 200);
+
+    let outcome = test.send(reap_legacy_comment_ix(&alice.pubkey(), &bob.pubkey(), "Old comment", &gone), &[&alice]);
+
+    outcome.assert_error(TwitterError::InvalidLegacyComment);
+    assert!(test.exists(&comment));
+}
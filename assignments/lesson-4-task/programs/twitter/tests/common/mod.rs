@@ -0,0 +1,640 @@
+//! Instruction builders and a LiteSVM wrapper for the twitter tests.
+//!
+//! Needs `twitter.so` from `anchor build`, read from `SBF_OUT_DIR` or
+//! `target/deploy`. States the instructions can't reach on their own
+//! (saturated counters, bans, legacy tweets) are written with `set_account`.
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction, InstructionError};
+use anchor_lang::{system_program, AccountDeserialize, AccountSerialize, Discriminator, InstructionData};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use solana_sdk::account::Account as SolanaAccount;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use twitter::instructions::TweetOptions;
+use twitter::states::*;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &twitter::ID).0
+}
+
+pub fn profile_pda(authority: &Pubkey) -> Pubkey {
+    pda(&[PROFILE_SEED.as_bytes(), authority.as_ref()])
+}
+
+pub fn handle_pda(handle: &str) -> Pubkey {
+    pda(&[HANDLE_SEED.as_bytes(), handle.as_bytes()])
+}
+
+pub fn tweet_pda(author: &Pubkey, index: u64) -> Pubkey {
+    pda(&[TWEET_SEED.as_bytes(), author.as_ref(), &index.to_le_bytes()])
+}
+
+pub fn legacy_tweet_pda(topic: &str, author: &Pubkey) -> Pubkey {
+    pda(&[topic.as_bytes(), TWEET_SEED.as_bytes(), author.as_ref()])
+}
+
+pub fn reaction_pda(author: &Pubkey, tweet: &Pubkey) -> Pubkey {
+    pda(&[TWEET_REACTION_SEED.as_bytes(), author.as_ref(), tweet.as_ref()])
+}
+
+pub fn comment_pda(tweet: &Pubkey, id: u64) -> Pubkey {
+    pda(&[COMMENT_SEED.as_bytes(), tweet.as_ref(), &id.to_le_bytes()])
+}
+
+pub fn legacy_comment_pda(author: &Pubkey, content: &str, tweet: &Pubkey) -> Pubkey {
+    pda(&[COMMENT_SEED.as_bytes(), author.as_ref(), hash(content.as_bytes()).as_ref(), tweet.as_ref()])
+}
+
+pub fn ban_pda(user: &Pubkey) -> Pubkey {
+    pda(&[BAN_SEED.as_bytes(), user.as_ref()])
+}
+
+pub fn treasury_pda() -> Pubkey {
+    pda(&[TREASURY_SEED.as_bytes()])
+}
+
+pub fn moderation_pda() -> Pubkey {
+    pda(&[MODERATION_SEED.as_bytes()])
+}
+
+pub fn poll_pda(tweet: &Pubkey) -> Pubkey {
+    pda(&[POLL_SEED.as_bytes(), tweet.as_ref()])
+}
+
+pub fn poll_vote_pda(poll: &Pubkey, voter: &Pubkey) -> Pubkey {
+    pda(&[POLL_VOTE_SEED.as_bytes(), poll.as_ref(), voter.as_ref()])
+}
+
+pub fn tag_index_pda(tag: &str, tweet: &Pubkey) -> Pubkey {
+    pda(&[TAG_SEED.as_bytes(), hash(tag.as_bytes()).as_ref(), tweet.as_ref()])
+}
+
+pub fn retweet_pda(author: &Pubkey, tweet: &Pubkey) -> Pubkey {
+    pda(&[RETWEET_SEED.as_bytes(), author.as_ref(), tweet.as_ref()])
+}
+
+pub fn mention_pda(profile: &Pubkey, tweet: &Pubkey) -> Pubkey {
+    pda(&[MENTION_SEED.as_bytes(), profile.as_ref(), tweet.as_ref()])
+}
+
+pub fn create_profile_ix(authority: &Pubkey, handle: &str) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::CreateProfileContext {
+            authority: *authority,
+            profile: profile_pda(authority),
+            handle_registry: handle_pda(handle),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::CreateProfile {
+            handle: handle.to_string(),
+            display_name: handle.to_string(),
+            bio: String::new(),
+        }
+        .data(),
+    }
+}
+
+pub fn initialize_ix(author: &Pubkey, index: u64, topic: &str, content: &str) -> Instruction {
+    initialize_with_options_ix(author, index, topic, content, TweetOptions::default())
+}
+
+/// `initialize_ix` for a tweet with a poll or schedule, passing the poll account when needed
+pub fn initialize_with_options_ix(
+    author: &Pubkey,
+    index: u64,
+    topic: &str,
+    content: &str,
+    options: TweetOptions,
+) -> Instruction {
+    let tweet = tweet_pda(author, index);
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::InitializeTweet {
+            tweet_authority: *author,
+            profile: profile_pda(author),
+            tweet,
+            ban: ban_pda(author),
+            gate_mint: None,
+            poll: options.poll.as_ref().map(|_| poll_pda(&tweet)),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::Initialize {
+            topic: topic.to_string(),
+            content: content.to_string(),
+            options,
+        }
+        .data(),
+    }
+}
+
+/// `initialize_ix` with the TagIndex and Mention accounts of `content` appended.
+/// Mentions need `mentioned` to hold the profile of each handle, in order.
+pub fn initialize_tagged_ix(
+    author: &Pubkey,
+    index: u64,
+    topic: &str,
+    content: &str,
+    mentioned: &[Pubkey],
+) -> Instruction {
+    let tweet = tweet_pda(author, index);
+    let mut mentioned = mentioned.iter();
+    let mut ix = initialize_ix(author, index, topic, content);
+    for tag in Tag::parse(content) {
+        match tag {
+            Tag::Hashtag(tag) => ix.accounts.push(AccountMeta::new(tag_index_pda(&tag, &tweet), false)),
+            Tag::Mention(handle) => {
+                let profile = mentioned.next().expect("profile of every mention");
+                ix.accounts.push(AccountMeta::new_readonly(handle_pda(&handle), false));
+                ix.accounts.push(AccountMeta::new(mention_pda(profile, &tweet), false));
+            }
+        }
+    }
+    ix
+}
+
+pub fn migrate_tweet_ix(author: &Pubkey, index: u64, topic: &str) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::MigrateTweetContext {
+            tweet_authority: *author,
+            profile: profile_pda(author),
+            legacy_tweet: legacy_tweet_pda(topic, author),
+            tweet: tweet_pda(author, index),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::MigrateTweet {
+            topic: topic.to_string(),
+        }
+        .data(),
+    }
+}
+
+fn add_reaction_accounts(author: &Pubkey, tweet: &Pubkey) -> Vec<AccountMeta> {
+    twitter::accounts::AddReactionContext {
+        reaction_author: *author,
+        tweet_reaction: reaction_pda(author, tweet),
+        tweet: *tweet,
+        ban: ban_pda(author),
+        gate_token_account: None,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None)
+}
+
+pub fn like_ix(author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: add_reaction_accounts(author, tweet),
+        data: twitter::instruction::LikeTweet {}.data(),
+    }
+}
+
+pub fn dislike_ix(author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: add_reaction_accounts(author, tweet),
+        data: twitter::instruction::DislikeTweet {}.data(),
+    }
+}
+
+pub fn reaction_remove_ix(author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::RemoveReactionContext {
+            reaction_author: *author,
+            tweet_reaction: reaction_pda(author, tweet),
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ReactionRemove {}.data(),
+    }
+}
+
+pub fn change_reaction_ix(author: &Pubkey, tweet: &Pubkey, reaction: ReactionType) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ChangeReactionContext {
+            reaction_author: *author,
+            tweet_reaction: reaction_pda(author, tweet),
+            tweet: *tweet,
+            ban: ban_pda(author),
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ChangeReaction { reaction }.data(),
+    }
+}
+
+pub fn comment_ix(
+    author: &Pubkey,
+    tweet: &Pubkey,
+    id: u64,
+    parent_comment: Option<Pubkey>,
+    content: &str,
+) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::AddCommentContext {
+            comment_author: *author,
+            comment: comment_pda(tweet, id),
+            tweet: *tweet,
+            parent_comment,
+            ban: ban_pda(author),
+            moderation_config: moderation_pda(),
+            gate_token_account: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::CommentTweet {
+            comment_content: content.to_string(),
+        }
+        .data(),
+    }
+}
+
+pub fn comment_remove_ix(author: &Pubkey, comment: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::RemoveCommentContext {
+            commentAuthor: *author,
+            comment: *comment,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::CommentRemove {}.data(),
+    }
+}
+
+pub fn reap_legacy_comment_ix(reaper: &Pubkey, comment_author: &Pubkey, content: &str, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ReapLegacyCommentContext {
+            reaper: *reaper,
+            comment: legacy_comment_pda(comment_author, content, tweet),
+            comment_author: *comment_author,
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ReapLegacyComment { content: content.to_string() }.data(),
+    }
+}
+
+pub fn close_mention_ix(user: &Pubkey, mention: &Pubkey, tweet_author: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::CloseMentionContext {
+            user: *user,
+            profile: profile_pda(user),
+            mention: *mention,
+            tweet_author: *tweet_author,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::CloseMention {}.data(),
+    }
+}
+
+pub fn reap_tag_index_ix(reaper: &Pubkey, tag: &str, tweet: &Pubkey, tweet_author: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ReapTagIndexContext {
+            reaper: *reaper,
+            tag_index: tag_index_pda(tag, tweet),
+            tweet_author: *tweet_author,
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ReapTagIndex {}.data(),
+    }
+}
+
+pub fn edit_tweet_ix(author: &Pubkey, tweet: &Pubkey, content: &str) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::EditTweetContext {
+            tweet_author: *author,
+            tweet: *tweet,
+            ban: ban_pda(author),
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::EditTweet {
+            content: content.to_string(),
+        }
+        .data(),
+    }
+}
+
+pub fn delete_tweet_ix(author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::DeleteTweetContext {
+            tweet_author: *author,
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::DeleteTweet {}.data(),
+    }
+}
+
+pub fn reap_reaction_ix(reaper: &Pubkey, reaction_author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ReapReactionContext {
+            reaper: *reaper,
+            tweet_reaction: reaction_pda(reaction_author, tweet),
+            reaction_author: *reaction_author,
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ReapReaction {}.data(),
+    }
+}
+
+pub fn retweet_ix(author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::RetweetContext {
+            retweet_author: *author,
+            retweet: retweet_pda(author, tweet),
+            tweet: *tweet,
+            ban: ban_pda(author),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::Retweet {}.data(),
+    }
+}
+
+pub fn reap_retweet_ix(reaper: &Pubkey, retweet_author: &Pubkey, tweet: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ReapRetweetContext {
+            reaper: *reaper,
+            retweet: retweet_pda(retweet_author, tweet),
+            retweet_author: *retweet_author,
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ReapRetweet {}.data(),
+    }
+}
+
+/// A vote for `option` of the poll on `tweet`
+pub fn vote_poll_ix(voter: &Pubkey, tweet: &Pubkey, option: u8) -> Instruction {
+    let poll = poll_pda(tweet);
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::VotePollContext {
+            voter: *voter,
+            poll,
+            tweet: *tweet,
+            poll_vote: poll_vote_pda(&poll, voter),
+            ban: ban_pda(voter),
+            gate_token_account: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::VotePoll { option }.data(),
+    }
+}
+
+pub fn close_poll_ix(closer: &Pubkey, tweet: &Pubkey, author: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ClosePollContext {
+            closer: *closer,
+            poll: poll_pda(tweet),
+            author: *author,
+            tweet: *tweet,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ClosePoll {}.data(),
+    }
+}
+
+pub fn reap_poll_vote_ix(reaper: &Pubkey, voter: &Pubkey, tweet: &Pubkey) -> Instruction {
+    let poll = poll_pda(tweet);
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::ReapPollVoteContext {
+            reaper: *reaper,
+            poll_vote: poll_vote_pda(&poll, voter),
+            voter: *voter,
+            poll,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::ReapPollVote {}.data(),
+    }
+}
+
+/// A lamport tip, paying the treasury's fee once the treasury exists
+pub fn tip_ix(tipper: &Pubkey, tweet: &Pubkey, author: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::TipTweetContext {
+            tipper: *tipper,
+            tweet: *tweet,
+            tweet_author: *author,
+            treasury: treasury_pda(),
+            mint: None,
+            tipper_token_account: None,
+            author_token_account: None,
+            treasury_token_account: None,
+            token_program: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::TipTweet { amount }.data(),
+    }
+}
+
+pub fn set_comment_depth_ix(admin: &Pubkey, max_comment_depth: u8) -> Instruction {
+    Instruction {
+        program_id: twitter::ID,
+        accounts: twitter::accounts::SetCommentDepthContext {
+            admin: *admin,
+            moderation_config: moderation_pda(),
+        }
+        .to_account_metas(None),
+        data: twitter::instruction::SetCommentDepth { max_comment_depth }.data(),
+    }
+}
+
+/// A tweet as `initialize` would create it, for injecting states the instructions can't reach
+pub fn tweet_state(author: &Pubkey, index: u64, reaction_counts: [u64; ReactionType::COUNT]) -> Tweet {
+    Tweet {
+        tweet_author: *author,
+        topic: "Injected".to_string(),
+        content: "Injected tweet".to_string(),
+        reaction_counts,
+        edit_count: 0,
+        last_edited_at: 0,
+        previous_content_hash: [0; 32],
+        index,
+        comment_count: 0,
+        total_tips: 0,
+        retweet_count: 0,
+        content_hash: [0; 32],
+        gate_mint: None,
+        min_balance: 0,
+        publish_at: 0,
+        expires_at: 0,
+        bump: 0,
+    }
+}
+
+/// LiteSVM's result for a transaction sent through `TwitterTest::send`
+pub struct TxOutcome(TransactionResult);
+
+impl TxOutcome {
+    fn logs(&self) -> &[String] {
+        match &self.0 {
+            Ok(meta) => &meta.logs,
+            Err(failed) => &failed.meta.logs,
+        }
+    }
+
+    /// Decodes every `E` the program emitted with `emit!`
+    pub fn events<E: AnchorDeserialize + Discriminator>(&self) -> Vec<E> {
+        self.logs()
+            .iter()
+            .filter_map(|line| line.strip_prefix("Program data: "))
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .filter_map(|bytes| {
+                let body = bytes.strip_prefix(E::DISCRIMINATOR)?;
+                Some(E::deserialize(&mut &body[..]).unwrap())
+            })
+            .collect()
+    }
+
+    pub fn assert_ok(&self) {
+        if let Err(failed) = &self.0 {
+            panic!("transaction failed: {:?}\n{:#?}", failed.err, failed.meta.logs);
+        }
+    }
+
+    /// Asserts the program failed with the given error code, e.g. a `TwitterError`
+    pub fn assert_error(&self, code: impl Into<u32>) {
+        let expected = TransactionError::InstructionError(0, InstructionError::Custom(code.into()));
+        match &self.0 {
+            Err(failed) if failed.err == expected => {}
+            _ => panic!("expected {expected:?}\n{:#?}", self.logs()),
+        }
+    }
+
+    /// Asserts an `init` failed on an address that is already taken
+    pub fn assert_already_in_use(&self) {
+        assert!(
+            self.0.is_err() && self.logs().iter().any(|line| line.contains("already in use")),
+            "expected an 'already in use' failure\n{:#?}",
+            self.logs()
+        );
+    }
+}
+
+/// A LiteSVM instance with the twitter program deployed
+pub struct TwitterTest {
+    svm: LiteSVM,
+    fee_payer: Keypair,
+}
+
+impl TwitterTest {
+    /// Gives every keypair in `users` 10 SOL to post and tip with
+    pub fn new(users: &[&Keypair]) -> Self {
+        let program = std::env::var_os("SBF_OUT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"))
+            .join("twitter.so");
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(twitter::ID, &program)
+            .unwrap_or_else(|err| panic!("can't load {}, run `anchor build`: {err:?}", program.display()));
+
+        let fee_payer = Keypair::new();
+        svm.airdrop(&fee_payer.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+        for user in users {
+            svm.airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        }
+
+        Self { svm, fee_payer }
+    }
+
+    /// Fees come out of a separate payer, so a user's balance only moves by what the instruction does
+    pub fn send(&mut self, ix: Instruction, signers: &[&Keypair]) -> TxOutcome {
+        let signers: Vec<&Keypair> = std::iter::once(&self.fee_payer).chain(signers.iter().copied()).collect();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.fee_payer.pubkey()),
+            &signers,
+            self.svm.latest_blockhash(),
+        );
+        let outcome = TxOutcome(self.svm.send_transaction(tx));
+        // Otherwise a repeated reaction or tip would be rejected as an already processed transaction
+        self.svm.expire_blockhash();
+        outcome
+    }
+
+    /// Creates a profile for `authority`, so it can start tweeting
+    pub fn create_profile(&mut self, authority: &Keypair, handle: &str) {
+        self.send(create_profile_ix(&authority.pubkey(), handle), &[authority]).assert_ok();
+    }
+
+    /// Posts the author's next tweet and returns its address
+    pub fn tweet(&mut self, author: &Keypair, topic: &str, content: &str) -> Pubkey {
+        let index = self.account::<Profile>(&profile_pda(&author.pubkey())).tweet_count;
+        self.send(initialize_ix(&author.pubkey(), index, topic, content), &[author]).assert_ok();
+        tweet_pda(&author.pubkey(), index)
+    }
+
+    pub fn rent(&self, space: usize) -> u64 {
+        self.svm.minimum_balance_for_rent_exemption(space)
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or_default()
+    }
+
+    /// Sends `lamports` to `address` without creating an account there, like a plain transfer would
+    pub fn fund(&mut self, address: &Pubkey, lamports: u64) {
+        self.svm.airdrop(address, lamports).unwrap();
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm.get_account(address).is_some_and(|account| account.lamports > 0)
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(address).expect("account should exist");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Writes `state` to `address` as a rent exempt program account of `space` bytes
+    pub fn set_account<T: AccountSerialize>(&mut self, address: &Pubkey, state: &T, space: usize) {
+        let mut data = Vec::with_capacity(space);
+        state.try_serialize(&mut data).unwrap();
+        self.set_raw_account(address, data, space);
+    }
+
+    /// Writes raw `data`, zero padded to `space` bytes, to `address` as a rent exempt program account
+    pub fn set_raw_account(&mut self, address: &Pubkey, mut data: Vec<u8>, space: usize) {
+        data.resize(space.max(data.len()), 0);
+        let account = SolanaAccount {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: twitter::ID,
+            ..SolanaAccount::default()
+        };
+        self.svm.set_account(*address, account).unwrap();
+    }
+}
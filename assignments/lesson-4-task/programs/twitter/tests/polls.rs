@@ -0,0 +1,215 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Space;
+use solana_sdk::signature::{Keypair, Signer};
+use twitter::errors::TwitterError;
+use twitter::instructions::{PollConfig, Schedule, TweetOptions};
+use twitter::states::*;
+
+use common::*;
+
+/// Open polls end at `i64::MAX`, ended ones at 0, so no test has to move the clock
+const OPEN: i64 = i64::MAX;
+const ENDED: i64 = 0;
+
+fn setup() -> (TwitterTest, Keypair, Keypair, Pubkey) {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut test = TwitterTest::new(&[&alice, &bob]);
+    test.create_profile(&alice, "alice");
+    let tweet = test.tweet(&alice, "Weekend", "Beach or mountains?");
+    (test, alice, bob, tweet)
+}
+
+/// Injects a poll on `tweet` ending at `ends_at`, with `votes` tallied on the first option
+fn inject_poll(test: &mut TwitterTest, tweet: &Pubkey, author: &Pubkey, ends_at: i64, votes: u64) {
+    let (_, bump) = Pubkey::find_program_address(&[POLL_SEED.as_bytes(), tweet.as_ref()], &twitter::ID);
+    let mut tally = [0; MAX_POLL_OPTIONS];
+    tally[0] = votes;
+    let state = Poll {
+        tweet: *tweet,
+        author: *author,
+        options: vec!["Beach".to_string(), "Mountains".to_string()],
+        votes: tally,
+        ends_at,
+        bump,
+    };
+    test.set_account(&poll_pda(tweet), &state, 8 + Poll::INIT_SPACE);
+}
+
+/// Injects the vote of `voter` for the first option of the poll on `tweet`
+fn inject_vote(test: &mut TwitterTest, tweet: &Pubkey, voter: &Pubkey) -> Pubkey {
+    let poll = poll_pda(tweet);
+    let (address, bump) = Pubkey::find_program_address(
+        &[POLL_VOTE_SEED.as_bytes(), poll.as_ref(), voter.as_ref()],
+        &twitter::ID,
+    );
+    let state = PollVote {
+        voter: *voter,
+        poll,
+        option: 0,
+        bump,
+    };
+    test.set_account(&address, &state, 8 + PollVote::INIT_SPACE);
+    address
+}
+
+#[test]
+fn vote_tallies_the_option_and_records_the_voter() {
+    let (mut test, alice, bob, tweet) = setup();
+    inject_poll(&mut test, &tweet, &alice.pubkey(), OPEN, 0);
+
+    test.send(vote_poll_ix(&bob.pubkey(), &tweet, 1), &[&bob]).assert_ok();
+
+    let poll = test.account::<Poll>(&poll_pda(&tweet));
+    assert_eq!(poll.votes[..2], [0, 1]);
+
+    let vote = test.account::<PollVote>(&poll_vote_pda(&poll_pda(&tweet), &bob.pubkey()));
+    assert_eq!(vote.voter, bob.pubkey());
+    assert_eq!(vote.option, 1);
+
+    let outcome = test.send(vote_poll_ix(&bob.pubkey(), &tweet, 0), &[&bob]);
+    outcome.assert_already_in_use();
+}
+
+#[test]
+fn vote_on_an_unpublished_tweet_fails() {
+    let (mut test, alice, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    let mut state = tweet_state(&alice.pubkey(), 1, [0; ReactionType::COUNT]);
+    state.publish_at = i64::MAX;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+    inject_poll(&mut test, &tweet, &alice.pubkey(), OPEN, 0);
+
+    let outcome = test.send(vote_poll_ix(&bob.pubkey(), &tweet, 0), &[&bob]);
+    outcome.assert_error(TwitterError::TweetNotPublished);
+}
+
+#[test]
+fn scheduled_tweet_with_a_poll_opens_both_at_publish_time() {
+    let (mut test, alice, bob, _) = setup();
+    let options = TweetOptions {
+        gate: None,
+        poll: Some(PollConfig {
+            options: vec!["Beach".to_string(), "Mountains".to_string()],
+            ends_at: OPEN,
+        }),
+        schedule: Some(Schedule {
+            publish_at: Some(i64::MAX - 1),
+            expires_at: None,
+        }),
+    };
+    test.send(
+        initialize_with_options_ix(&alice.pubkey(), 1, "Weekend", "Vote next week", options),
+        &[&alice],
+    )
+    .assert_ok();
+
+    let tweet = tweet_pda(&alice.pubkey(), 1);
+    assert_eq!(test.account::<Tweet>(&tweet).publish_at, i64::MAX - 1);
+    let poll = test.account::<Poll>(&poll_pda(&tweet));
+    assert_eq!(poll.author, alice.pubkey());
+    assert_eq!(poll.ends_at, OPEN);
+
+    let outcome = test.send(vote_poll_ix(&bob.pubkey(), &tweet, 0), &[&bob]);
+    outcome.assert_error(TwitterError::TweetNotPublished);
+}
+
+#[test]
+fn poll_options_and_poll_account_go_together() {
+    let (mut test, alice, _, _) = setup();
+    let options = TweetOptions {
+        poll: Some(PollConfig {
+            options: vec!["Beach".to_string(), "Mountains".to_string()],
+            ends_at: OPEN,
+        }),
+        ..TweetOptions::default()
+    };
+    let with_poll = initialize_with_options_ix(&alice.pubkey(), 1, "Weekend", "Beach?", options);
+    let plain = initialize_ix(&alice.pubkey(), 1, "Weekend", "Beach?");
+
+    // Options without the account
+    let mut ix = plain.clone();
+    ix.data = with_poll.data.clone();
+    test.send(ix, &[&alice]).assert_error(TwitterError::PollAccountMismatch);
+
+    // The account without options
+    let mut ix = with_poll;
+    ix.data = plain.data;
+    test.send(ix, &[&alice]).assert_error(TwitterError::PollAccountMismatch);
+}
+
+#[test]
+fn vote_on_a_gated_tweet_without_tokens_fails() {
+    let (mut test, alice, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    let mut state = tweet_state(&alice.pubkey(), 1, [0; ReactionType::COUNT]);
+    state.gate_mint = Some(Pubkey::new_unique());
+    state.min_balance = 1;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+    inject_poll(&mut test, &tweet, &alice.pubkey(), OPEN, 0);
+
+    let outcome = test.send(vote_poll_ix(&bob.pubkey(), &tweet, 0), &[&bob]);
+    outcome.assert_error(TwitterError::TokenGateNotMet);
+}
+
+#[test]
+fn author_closes_an_ended_poll_and_gets_the_rent() {
+    let (mut test, alice, bob, tweet) = setup();
+    inject_poll(&mut test, &tweet, &alice.pubkey(), OPEN, 0);
+
+    let outcome = test.send(close_poll_ix(&alice.pubkey(), &tweet, &alice.pubkey()), &[&alice]);
+    outcome.assert_error(TwitterError::PollNotEnded);
+
+    inject_poll(&mut test, &tweet, &alice.pubkey(), ENDED, 3);
+    let outcome = test.send(close_poll_ix(&bob.pubkey(), &tweet, &alice.pubkey()), &[&bob]);
+    outcome.assert_error(TwitterError::Unauthorized);
+
+    let poll_lamports = test.balance(&poll_pda(&tweet));
+    let alice_before = test.balance(&alice.pubkey());
+    test.send(close_poll_ix(&alice.pubkey(), &tweet, &alice.pubkey()), &[&alice]).assert_ok();
+
+    assert!(!test.exists(&poll_pda(&tweet)));
+    assert_eq!(test.balance(&alice.pubkey()), alice_before + poll_lamports);
+}
+
+#[test]
+fn anyone_closes_the_poll_of_a_deleted_tweet() {
+    let (mut test, alice, bob, tweet) = setup();
+    inject_poll(&mut test, &tweet, &alice.pubkey(), OPEN, 0);
+    test.send(delete_tweet_ix(&alice.pubkey(), &tweet), &[&alice]).assert_ok();
+
+    let poll_lamports = test.balance(&poll_pda(&tweet));
+    let alice_before = test.balance(&alice.pubkey());
+    test.send(close_poll_ix(&bob.pubkey(), &tweet, &alice.pubkey()), &[&bob]).assert_ok();
+
+    assert!(!test.exists(&poll_pda(&tweet)));
+    assert_eq!(test.balance(&alice.pubkey()), alice_before + poll_lamports);
+}
+
+#[test]
+fn votes_are_reaped_once_the_poll_ended_or_closed() {
+    let (mut test, alice, bob, tweet) = setup();
+    inject_poll(&mut test, &tweet, &alice.pubkey(), OPEN, 1);
+    let vote = inject_vote(&mut test, &tweet, &bob.pubkey());
+
+    let outcome = test.send(reap_poll_vote_ix(&alice.pubkey(), &bob.pubkey(), &tweet), &[&alice]);
+    outcome.assert_error(TwitterError::PollNotEnded);
+
+    // Ended, the tally keeps the vote
+    inject_poll(&mut test, &tweet, &alice.pubkey(), ENDED, 1);
+    let vote_lamports = test.balance(&vote);
+    let bob_before = test.balance(&bob.pubkey());
+    test.send(reap_poll_vote_ix(&alice.pubkey(), &bob.pubkey(), &tweet), &[&alice]).assert_ok();
+    assert!(!test.exists(&vote));
+    assert_eq!(test.balance(&bob.pubkey()), bob_before + vote_lamports);
+    assert_eq!(test.account::<Poll>(&poll_pda(&tweet)).votes[0], 1);
+
+    // Closed, nothing is left to read
+    let carol = Pubkey::new_unique();
+    let vote = inject_vote(&mut test, &tweet, &carol);
+    test.send(close_poll_ix(&alice.pubkey(), &tweet, &alice.pubkey()), &[&alice]).assert_ok();
+    test.send(reap_poll_vote_ix(&alice.pubkey(), &carol, &tweet), &[&alice]).assert_ok();
+    assert!(!test.exists(&vote));
+}
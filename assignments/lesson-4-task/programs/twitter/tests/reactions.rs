@@ -0,0 +1,256 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Space;
+use solana_sdk::signature::{Keypair, Signer};
+use twitter::errors::TwitterError;
+use twitter::events::{AddReactionEvent, ChangeReactionEvent, RemoveReactionEvent};
+use twitter::states::*;
+
+use common::*;
+
+fn setup() -> (TwitterTest, Keypair, Keypair, Pubkey) {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut test = TwitterTest::new(&[&alice, &bob]);
+    test.create_profile(&alice, "alice");
+    let tweet = test.tweet(&alice, "Hello", "React to this");
+    (test, alice, bob, tweet)
+}
+
+/// Injects a tweet with the given counters and a reaction of `author` on it,
+/// a state `like_tweet` and `dislike_tweet` can't produce on their own
+fn inject_reaction(
+    test: &mut TwitterTest,
+    author: &Pubkey,
+    reaction: ReactionType,
+    reaction_counts: [u64; ReactionType::COUNT],
+) -> Pubkey {
+    let tweet = Pubkey::new_unique();
+    test.set_account(&tweet, &tweet_state(&Pubkey::new_unique(), 0, reaction_counts), 8 + Tweet::INIT_SPACE);
+
+    let address = reaction_pda(author, &tweet);
+    let (_, bump) = Pubkey::find_program_address(
+        &[TWEET_REACTION_SEED.as_bytes(), author.as_ref(), tweet.as_ref()],
+        &twitter::ID,
+    );
+    let state = Reaction {
+        reactionAuthor: *author,
+        parent_tweet: tweet,
+        reaction,
+        bump,
+    };
+    test.set_account(&address, &state, Reaction::SPACE);
+    tweet
+}
+
+#[test]
+fn like_tweet_creates_reaction_and_counts_it() {
+    let (mut test, _, bob, tweet) = setup();
+
+    let outcome = test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]);
+    outcome.assert_ok();
+
+    let reaction = test.account::<Reaction>(&reaction_pda(&bob.pubkey(), &tweet));
+    assert_eq!(reaction.reactionAuthor, bob.pubkey());
+    assert_eq!(reaction.parent_tweet, tweet);
+    assert!(reaction.reaction == ReactionType::Like);
+
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.likes(), 1);
+    assert_eq!(state.dislikes(), 0);
+
+    let events = outcome.events::<AddReactionEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].reaction_author, bob.pubkey());
+    assert_eq!(events[0].tweet, tweet);
+}
+
+#[test]
+fn dislike_tweet_creates_reaction_and_counts_it() {
+    let (mut test, _, bob, tweet) = setup();
+
+    test.send(dislike_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+
+    let reaction = test.account::<Reaction>(&reaction_pda(&bob.pubkey(), &tweet));
+    assert!(reaction.reaction == ReactionType::Dislike);
+
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.likes(), 0);
+    assert_eq!(state.dislikes(), 1);
+}
+
+#[test]
+fn second_reaction_on_the_same_tweet_fails() {
+    let (mut test, _, bob, tweet) = setup();
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_already_in_use();
+    test.send(dislike_ix(&bob.pubkey(), &tweet), &[&bob]).assert_already_in_use();
+
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.likes(), 1);
+    assert_eq!(state.dislikes(), 0);
+}
+
+#[test]
+fn reaction_remove_decrements_counter_and_refunds_rent() {
+    let (mut test, _, bob, tweet) = setup();
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+
+    let reaction = reaction_pda(&bob.pubkey(), &tweet);
+    let reaction_lamports = test.balance(&reaction);
+    let bob_before = test.balance(&bob.pubkey());
+
+    let outcome = test.send(reaction_remove_ix(&bob.pubkey(), &tweet), &[&bob]);
+    outcome.assert_ok();
+
+    assert!(!test.exists(&reaction));
+    assert_eq!(test.balance(&bob.pubkey()), bob_before + reaction_lamports);
+    assert_eq!(test.account::<Tweet>(&tweet).likes(), 0);
+
+    let events = outcome.events::<RemoveReactionEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].reaction_author, bob.pubkey());
+}
+
+#[test]
+fn change_reaction_moves_the_count_and_reports_both_types() {
+    let (mut test, _, bob, tweet) = setup();
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+
+    let outcome = test
+        .send(change_reaction_ix(&bob.pubkey(), &tweet, ReactionType::Dislike), &[&bob]);
+    outcome.assert_ok();
+
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.likes(), 0);
+    assert_eq!(state.dislikes(), 1);
+
+    let events = outcome.events::<ChangeReactionEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].reaction_author, bob.pubkey());
+    assert_eq!(events[0].tweet, tweet);
+    assert!(events[0].old == ReactionType::Like);
+    assert!(events[0].new == ReactionType::Dislike);
+}
+
+#[test]
+fn like_tweet_after_expiry_fails() {
+    let (mut test, alice, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    let mut state = tweet_state(&alice.pubkey(), 1, [0; ReactionType::COUNT]);
+    state.expires_at = 1;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+
+    let outcome = test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]);
+
+    outcome.assert_error(TwitterError::TweetExpired);
+}
+
+#[test]
+fn like_gated_tweet_without_token_account_fails() {
+    let (mut test, alice, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    let mut state = tweet_state(&alice.pubkey(), 1, [0; ReactionType::COUNT]);
+    state.gate_mint = Some(Pubkey::new_unique());
+    state.min_balance = 1;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+
+    let outcome = test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]);
+
+    outcome.assert_error(TwitterError::TokenGateNotMet);
+}
+
+#[test]
+fn change_reaction_on_an_expired_tweet_fails() {
+    let (mut test, _, bob, _) = setup();
+    let tweet = inject_reaction(&mut test, &bob.pubkey(), ReactionType::Like, [1, 0, 0, 0, 0, 0, 0, 0]);
+    let mut state = test.account::<Tweet>(&tweet);
+    state.expires_at = 1;
+    test.set_account(&tweet, &state, 8 + Tweet::INIT_SPACE);
+
+    let outcome = test.send(change_reaction_ix(&bob.pubkey(), &tweet, ReactionType::Love), &[&bob]);
+    outcome.assert_error(TwitterError::TweetExpired);
+}
+
+#[test]
+fn reaction_remove_of_another_users_reaction_fails() {
+    let (mut test, alice, bob, tweet) = setup();
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+
+    // Alice's reaction address is derived from her key, so bob's reaction can't be passed
+    let outcome = test.send(reaction_remove_ix(&alice.pubkey(), &tweet), &[&alice]);
+
+    outcome.assert_error(ErrorCode::AccountNotInitialized);
+    assert!(test.exists(&reaction_pda(&bob.pubkey(), &tweet)));
+}
+
+#[test]
+fn reaction_remove_below_zero_likes_fails() {
+    let (mut test, _, bob, _) = setup();
+    let tweet = inject_reaction(&mut test, &bob.pubkey(), ReactionType::Like, [0; ReactionType::COUNT]);
+
+    let outcome = test.send(reaction_remove_ix(&bob.pubkey(), &tweet), &[&bob]);
+
+    outcome.assert_error(TwitterError::MinLikesReached);
+}
+
+#[test]
+fn reaction_remove_below_zero_dislikes_fails() {
+    let (mut test, _, bob, _) = setup();
+    let tweet = inject_reaction(&mut test, &bob.pubkey(), ReactionType::Dislike, [0; ReactionType::COUNT]);
+
+    let outcome = test.send(reaction_remove_ix(&bob.pubkey(), &tweet), &[&bob]);
+
+    outcome.assert_error(TwitterError::MinDislikesReached);
+}
+
+#[test]
+fn like_and_dislike_past_the_maximum_fail() {
+    let (mut test, _, bob, _) = setup();
+    let tweet = Pubkey::new_unique();
+    test.set_account(&tweet, &tweet_state(&Pubkey::new_unique(), 0, [u64::MAX; ReactionType::COUNT]), 8 + Tweet::INIT_SPACE);
+
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_error(TwitterError::MaxLikesReached);
+    test.send(dislike_ix(&bob.pubkey(), &tweet), &[&bob])
+        .assert_error(TwitterError::MaxDislikesReached);
+}
+
+#[test]
+fn like_tweet_rejects_banned_user() {
+    let (mut test, _, bob, tweet) = setup();
+    let ban = Ban {
+        user: bob.pubkey(),
+        banned_by: Pubkey::new_unique(),
+        banned_at: 0,
+        bump: 0,
+    };
+    test.set_account(&ban_pda(&bob.pubkey()), &ban, 8 + Ban::INIT_SPACE);
+
+    let outcome = test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]);
+
+    outcome.assert_error(TwitterError::UserBanned);
+}
+
+#[test]
+fn reactions_of_a_deleted_tweet_stay_detached_and_can_be_reaped() {
+    let (mut test, alice, bob, tweet) = setup();
+    test.send(like_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+    test.send(delete_tweet_ix(&alice.pubkey(), &tweet), &[&alice]).assert_ok();
+
+    // Posting again, even on the same topic, gets the next index and a fresh address
+    let reposted = test.tweet(&alice, "Hello", "React to this");
+    assert_ne!(reposted, tweet);
+    test.send(like_ix(&bob.pubkey(), &reposted), &[&bob]).assert_ok();
+    assert_eq!(test.account::<Tweet>(&reposted).likes(), 1);
+
+    let reaction = reaction_pda(&bob.pubkey(), &tweet);
+    let reaction_lamports = test.balance(&reaction);
+    let bob_before = test.balance(&bob.pubkey());
+    test.send(reap_reaction_ix(&alice.pubkey(), &bob.pubkey(), &tweet), &[&alice]).assert_ok();
+
+    assert!(!test.exists(&reaction));
+    assert_eq!(test.balance(&bob.pubkey()), bob_before + reaction_lamports);
+}
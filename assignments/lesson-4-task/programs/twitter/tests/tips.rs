@@ -0,0 +1,90 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Space;
+use solana_sdk::signature::{Keypair, Signer};
+use twitter::errors::TwitterError;
+use twitter::events::TipEvent;
+use twitter::states::*;
+
+use common::*;
+
+const TIP: u64 = 1_000_000;
+const FEE_BPS: u16 = 250;
+
+fn setup() -> (TwitterTest, Keypair, Keypair, Pubkey) {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut test = TwitterTest::new(&[&alice, &bob]);
+    test.create_profile(&alice, "alice");
+    let tweet = test.tweet(&alice, "Hello", "Tip me");
+    (test, alice, bob, tweet)
+}
+
+fn setup_treasury(test: &mut TwitterTest) {
+    let (_, bump) = Pubkey::find_program_address(&[TREASURY_SEED.as_bytes()], &twitter::ID);
+    let treasury = Treasury {
+        authority: Pubkey::new_unique(),
+        fee_bps: FEE_BPS,
+        bump,
+    };
+    test.set_account(&treasury_pda(), &treasury, 8 + Treasury::INIT_SPACE);
+}
+
+#[test]
+fn tip_before_the_treasury_exists_pays_the_author_in_full() {
+    let (mut test, alice, bob, tweet) = setup();
+    let alice_before = test.balance(&alice.pubkey());
+
+    let outcome = test.send(tip_ix(&bob.pubkey(), &tweet, &alice.pubkey(), TIP), &[&bob]);
+    outcome.assert_ok();
+
+    assert_eq!(test.balance(&alice.pubkey()), alice_before + TIP);
+    assert_eq!(test.account::<Tweet>(&tweet).total_tips, 1);
+
+    let events = outcome.events::<TipEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].amount, TIP);
+    assert_eq!(events[0].fee, 0);
+}
+
+#[test]
+fn tip_through_treasury_takes_the_fee() {
+    let (mut test, alice, bob, tweet) = setup();
+    setup_treasury(&mut test);
+    let alice_before = test.balance(&alice.pubkey());
+    let treasury_before = test.balance(&treasury_pda());
+
+    let outcome = test.send(tip_ix(&bob.pubkey(), &tweet, &alice.pubkey(), TIP), &[&bob]);
+    outcome.assert_ok();
+
+    let fee = TIP * FEE_BPS as u64 / 10_000;
+    assert_eq!(test.balance(&alice.pubkey()), alice_before + TIP - fee);
+    assert_eq!(test.balance(&treasury_pda()), treasury_before + fee);
+    assert_eq!(outcome.events::<TipEvent>()[0].fee, fee);
+}
+
+#[test]
+fn tip_cannot_skip_the_treasury_fee() {
+    let (mut test, alice, bob, tweet) = setup();
+    setup_treasury(&mut test);
+
+    // Passing any other account in place of the treasury would dodge the fee
+    let mut ix = tip_ix(&bob.pubkey(), &tweet, &alice.pubkey(), TIP);
+    ix.accounts[3].pubkey = bob.pubkey();
+    let outcome = test.send(ix, &[&bob]);
+
+    outcome.assert_error(ErrorCode::ConstraintSeeds);
+    assert_eq!(test.account::<Tweet>(&tweet).total_tips, 0);
+}
+
+#[test]
+fn tip_of_nothing_fails() {
+    let (mut test, alice, bob, tweet) = setup();
+
+    let outcome = test.send(tip_ix(&bob.pubkey(), &tweet, &alice.pubkey(), 0), &[&bob]);
+
+    outcome.assert_error(TwitterError::InvalidTipAmount);
+    assert_eq!(test.account::<Tweet>(&tweet).total_tips, 0);
+}
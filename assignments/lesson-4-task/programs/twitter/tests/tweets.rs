@@ -0,0 +1,467 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::{AnchorSerialize, Discriminator, Space};
+use solana_sdk::signature::{Keypair, Signer};
+use twitter::errors::TwitterError;
+use twitter::events::InitializeTweetEvent;
+use twitter::instructions::{PollConfig, Schedule, TokenGate, TweetOptions};
+use twitter::states::*;
+
+use common::*;
+
+fn setup() -> (TwitterTest, Keypair) {
+    let alice = Keypair::new();
+    let mut test = TwitterTest::new(&[&alice]);
+    test.create_profile(&alice, "alice");
+    (test, alice)
+}
+
+/// Alice's first tweet with `options`
+fn initialize_with(options: TweetOptions) -> TxOutcome {
+    let (mut test, alice) = setup();
+    test.send(
+        initialize_with_options_ix(&alice.pubkey(), 0, "Topic", "Content", options),
+        &[&alice],
+    )
+}
+
+fn gated(min_balance: u64) -> TweetOptions {
+    TweetOptions {
+        gate: Some(TokenGate {
+            content_hash: [7; 32],
+            min_balance,
+        }),
+        ..TweetOptions::default()
+    }
+}
+
+fn with_poll(options: &[&str], ends_at: i64) -> TweetOptions {
+    TweetOptions {
+        poll: Some(PollConfig {
+            options: options.iter().map(|option| option.to_string()).collect(),
+            ends_at,
+        }),
+        ..TweetOptions::default()
+    }
+}
+
+/// Alice and bob with profiles, bob to be mentioned as "@bob"
+fn setup_with_bob() -> (TwitterTest, Keypair, Keypair) {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut test = TwitterTest::new(&[&alice, &bob]);
+    test.create_profile(&alice, "alice");
+    test.create_profile(&bob, "bob");
+    (test, alice, bob)
+}
+
+#[test]
+fn initialize_stores_tweet_and_emits_event() {
+    let (mut test, alice) = setup();
+    let tweet = tweet_pda(&alice.pubkey(), 0);
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, "Hello", "First tweet"), &[&alice]);
+    outcome.assert_ok();
+
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.tweet_author, alice.pubkey());
+    assert_eq!(state.topic, "Hello");
+    assert_eq!(state.content, "First tweet");
+    assert_eq!(state.reaction_counts, [0; ReactionType::COUNT]);
+    assert_eq!(state.index, 0);
+    assert_eq!(state.comment_count, 0);
+    assert_eq!(state.content_hash, hash(b"First tweet").to_bytes());
+
+    let profile = test.account::<Profile>(&profile_pda(&alice.pubkey()));
+    assert_eq!(profile.tweet_count, 1);
+
+    let events = outcome.events::<InitializeTweetEvent>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tweet, tweet);
+    assert_eq!(events[0].tweet_author, alice.pubkey());
+    assert_eq!(events[0].index, 0);
+    assert_eq!(events[0].content_hash, hash(b"First tweet").to_bytes());
+}
+
+#[test]
+fn initialize_allows_the_same_topic_twice() {
+    let (mut test, alice) = setup();
+
+    let first = test.tweet(&alice, "Hello", "First tweet");
+    let second = test.tweet(&alice, "Hello", "First tweet");
+
+    assert_ne!(first, second);
+    assert_eq!(test.account::<Tweet>(&second).index, 1);
+}
+
+#[test]
+fn initialize_counts_limits_in_characters() {
+    let (mut test, alice) = setup();
+    let topic = "é".repeat(TOPIC_LENGTH);
+    let content = "🚀".repeat(150);
+
+    let tweet = test.tweet(&alice, &topic, &content);
+
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.topic, topic);
+    assert_eq!(state.content, content);
+}
+
+#[test]
+fn initialize_rejects_topic_too_long() {
+    let (mut test, alice) = setup();
+    let topic = "A".repeat(TOPIC_LENGTH + 1);
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, &topic, "Content"), &[&alice]);
+
+    outcome.assert_error(TwitterError::TopicTooLong);
+    assert!(!test.exists(&tweet_pda(&alice.pubkey(), 0)));
+}
+
+#[test]
+fn initialize_rejects_content_too_long() {
+    let (mut test, alice) = setup();
+    let content = "B".repeat(CONTENT_LENGTH + 1);
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, "Topic", &content), &[&alice]);
+
+    outcome.assert_error(TwitterError::ContentTooLong);
+}
+
+#[test]
+fn initialize_rejects_topic_over_the_byte_cap() {
+    let (mut test, alice) = setup();
+    let topic = "🚀".repeat(TOPIC_MAX_BYTES / 4 + 1);
+    assert!(topic.chars().count() <= TOPIC_LENGTH);
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, &topic, "Content"), &[&alice]);
+
+    outcome.assert_error(TwitterError::TopicTooLong);
+}
+
+#[test]
+fn initialize_rejects_content_over_the_byte_cap() {
+    let (mut test, alice) = setup();
+    let content = "🚀".repeat(CONTENT_MAX_BYTES / 4 + 1);
+    assert!(content.chars().count() <= CONTENT_LENGTH);
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, "Topic", &content), &[&alice]);
+
+    outcome.assert_error(TwitterError::ContentTooLong);
+}
+
+#[test]
+fn initialize_rejects_blank_topic_and_content() {
+    let (mut test, alice) = setup();
+
+    test.send(initialize_ix(&alice.pubkey(), 0, " ", "Content"), &[&alice])
+        .assert_error(TwitterError::TopicEmpty);
+    test.send(initialize_ix(&alice.pubkey(), 0, "Topic", "\t\n"), &[&alice])
+        .assert_error(TwitterError::ContentEmpty);
+}
+
+#[test]
+fn initialize_rejects_banned_author() {
+    let (mut test, alice) = setup();
+    let ban = Ban {
+        user: alice.pubkey(),
+        banned_by: Pubkey::new_unique(),
+        banned_at: 0,
+        bump: 0,
+    };
+    test.set_account(&ban_pda(&alice.pubkey()), &ban, 8 + Ban::INIT_SPACE);
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, "Topic", "Content"), &[&alice]);
+
+    outcome.assert_error(TwitterError::UserBanned);
+}
+
+#[test]
+fn edit_tweet_rejects_banned_author() {
+    let (mut test, alice) = setup();
+    let tweet = test.tweet(&alice, "Topic", "Content");
+    let ban = Ban {
+        user: alice.pubkey(),
+        banned_by: Pubkey::new_unique(),
+        banned_at: 0,
+        bump: 0,
+    };
+    test.set_account(&ban_pda(&alice.pubkey()), &ban, 8 + Ban::INIT_SPACE);
+
+    let outcome = test.send(edit_tweet_ix(&alice.pubkey(), &tweet, "Rewritten"), &[&alice]);
+
+    outcome.assert_error(TwitterError::UserBanned);
+    assert_eq!(test.account::<Tweet>(&tweet).content, "Content");
+}
+
+#[test]
+fn initialize_rejects_gate_without_minimum_balance() {
+    initialize_with(gated(0)).assert_error(TwitterError::InvalidGateBalance);
+}
+
+#[test]
+fn initialize_rejects_gate_without_mint() {
+    initialize_with(gated(100)).assert_error(TwitterError::MissingGateMint);
+}
+
+#[test]
+fn initialize_rejects_poll_with_a_single_option() {
+    initialize_with(with_poll(&["Yes"], i64::MAX)).assert_error(TwitterError::InvalidPollOptionCount);
+}
+
+#[test]
+fn initialize_rejects_blank_poll_option() {
+    initialize_with(with_poll(&["Yes", "  "], i64::MAX)).assert_error(TwitterError::PollOptionEmpty);
+}
+
+#[test]
+fn initialize_rejects_poll_option_too_long() {
+    let long = "x".repeat(POLL_OPTION_LENGTH + 1);
+    initialize_with(with_poll(&["Yes", &long], i64::MAX)).assert_error(TwitterError::PollOptionTooLong);
+}
+
+#[test]
+fn initialize_rejects_poll_ending_in_the_past() {
+    initialize_with(with_poll(&["Yes", "No"], 0)).assert_error(TwitterError::InvalidPollEnd);
+}
+
+#[test]
+fn initialize_rejects_expiry_before_publish_time() {
+    let options = TweetOptions {
+        schedule: Some(Schedule {
+            publish_at: Some(1_000),
+            expires_at: Some(1_000),
+        }),
+        ..TweetOptions::default()
+    };
+
+    initialize_with(options).assert_error(TwitterError::InvalidSchedule);
+}
+
+#[test]
+fn initialize_rejects_author_out_of_tweet_indices() {
+    let (mut test, alice) = setup();
+    let profile = profile_pda(&alice.pubkey());
+    let mut state = test.account::<Profile>(&profile);
+    state.tweet_count = u64::MAX;
+    test.set_account(&profile, &state, 8 + Profile::INIT_SPACE);
+
+    let outcome = test
+        .send(initialize_ix(&alice.pubkey(), u64::MAX, "Topic", "Content"), &[&alice]);
+
+    outcome.assert_error(TwitterError::MaxTweetsReached);
+}
+
+#[test]
+fn initialize_indexes_hashtags_and_mentions() {
+    let (mut test, alice, bob) = setup_with_bob();
+    let bob_profile = profile_pda(&bob.pubkey());
+    let tweet = tweet_pda(&alice.pubkey(), 0);
+
+    test.send(
+        initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "#Solana is fast, ask @Bob #solana #rust_lang", &[bob_profile]),
+        &[&alice],
+    )
+    .assert_ok();
+
+    for tag in ["solana", "rust_lang"] {
+        let index = test.account::<TagIndex>(&tag_index_pda(tag, &tweet));
+        assert_eq!(index.tag_hash, hash(tag.as_bytes()).to_bytes());
+        assert_eq!(index.tweet, tweet);
+        assert_eq!(index.tweet_author, alice.pubkey());
+    }
+    let mention = test.account::<Mention>(&mention_pda(&bob_profile, &tweet));
+    assert_eq!(mention.profile, bob_profile);
+    assert_eq!(mention.tweet, tweet);
+    assert_eq!(mention.tweet_author, alice.pubkey());
+}
+
+#[test]
+fn initialize_skips_mentions_of_unclaimed_handles() {
+    let (mut test, alice) = setup();
+    let nobody = Pubkey::new_unique();
+
+    test.send(
+        initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "Hello @nobody", &[nobody]),
+        &[&alice],
+    )
+    .assert_ok();
+
+    assert!(!test.exists(&mention_pda(&nobody, &tweet_pda(&alice.pubkey(), 0))));
+}
+
+#[test]
+fn initialize_rejects_too_many_tags() {
+    let (mut test, alice) = setup();
+    let content = (0..=MAX_TAGS).map(|i| format!("#tag{i}")).collect::<Vec<_>>().join(" ");
+
+    let outcome = test.send(initialize_ix(&alice.pubkey(), 0, "Tags", &content), &[&alice]);
+
+    outcome.assert_error(TwitterError::TooManyTags);
+}
+
+#[test]
+fn initialize_rejects_missing_tag_accounts() {
+    let (mut test, alice) = setup();
+    let mut ix = initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "#one #two", &[]);
+    ix.accounts.pop();
+
+    let outcome = test.send(ix, &[&alice]);
+
+    outcome.assert_error(TwitterError::TagAccountsMismatch);
+}
+
+#[test]
+fn initialize_indexes_composed_and_decomposed_hashtags_alike() {
+    let (mut test, alice) = setup();
+    let tweet = tweet_pda(&alice.pubkey(), 0);
+
+    // "cafe" followed by a combining acute accent, indexed as the composed "café"
+    test.send(initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "#Cafe\u{301}", &[]), &[&alice])
+        .assert_ok();
+
+    let index = test.account::<TagIndex>(&tag_index_pda("caf\u{e9}", &tweet));
+    assert_eq!(index.tag_hash, hash("caf\u{e9}".as_bytes()).to_bytes());
+}
+
+#[test]
+fn initialize_indexes_hashtags_at_prefunded_addresses() {
+    let (mut test, alice) = setup();
+    let tweet = tweet_pda(&alice.pubkey(), 0);
+    let index = tag_index_pda("solana", &tweet);
+    test.fund(&index, 1);
+
+    test.send(initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "#solana", &[]), &[&alice])
+        .assert_ok();
+
+    assert_eq!(test.account::<TagIndex>(&index).tweet, tweet);
+    assert_eq!(test.balance(&index), test.rent(8 + TagIndex::INIT_SPACE));
+}
+
+#[test]
+fn reap_tag_index_refunds_tweet_author_once_tweet_is_gone() {
+    let (mut test, alice) = setup();
+    let reaper = Keypair::new();
+    let tweet = tweet_pda(&alice.pubkey(), 0);
+    let index = tag_index_pda("solana", &tweet);
+    test.send(initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "#solana", &[]), &[&alice])
+        .assert_ok();
+
+    test.send(reap_tag_index_ix(&reaper.pubkey(), "solana", &tweet, &alice.pubkey()), &[&reaper])
+        .assert_error(TwitterError::TweetStillExists);
+
+    test.send(delete_tweet_ix(&alice.pubkey(), &tweet), &[&alice]).assert_ok();
+    let index_lamports = test.balance(&index);
+    let alice_before = test.balance(&alice.pubkey());
+    test.send(reap_tag_index_ix(&reaper.pubkey(), "solana", &tweet, &alice.pubkey()), &[&reaper])
+        .assert_ok();
+
+    assert!(!test.exists(&index));
+    assert_eq!(test.balance(&alice.pubkey()), alice_before + index_lamports);
+}
+
+#[test]
+fn reap_retweet_refunds_the_retweeter_once_the_tweet_is_gone() {
+    let (mut test, alice, bob) = setup_with_bob();
+    let tweet = test.tweet(&alice, "Topic", "Share this");
+    test.send(retweet_ix(&bob.pubkey(), &tweet), &[&bob]).assert_ok();
+    let retweet = retweet_pda(&bob.pubkey(), &tweet);
+
+    test.send(reap_retweet_ix(&alice.pubkey(), &bob.pubkey(), &tweet), &[&alice])
+        .assert_error(TwitterError::TweetStillExists);
+
+    test.send(delete_tweet_ix(&alice.pubkey(), &tweet), &[&alice]).assert_ok();
+    let retweet_lamports = test.balance(&retweet);
+    let bob_before = test.balance(&bob.pubkey());
+    test.send(reap_retweet_ix(&alice.pubkey(), &bob.pubkey(), &tweet), &[&alice]).assert_ok();
+
+    assert!(!test.exists(&retweet));
+    assert_eq!(test.balance(&bob.pubkey()), bob_before + retweet_lamports);
+}
+
+#[test]
+fn close_mention_refunds_tweet_author_and_rejects_others() {
+    let (mut test, alice, bob) = setup_with_bob();
+    let bob_profile = profile_pda(&bob.pubkey());
+    test.send(
+        initialize_tagged_ix(&alice.pubkey(), 0, "Tags", "Hi @bob", &[bob_profile]),
+        &[&alice],
+    )
+    .assert_ok();
+    let mention = mention_pda(&bob_profile, &tweet_pda(&alice.pubkey(), 0));
+
+    // Alice wrote the tweet but wasn't mentioned, her own profile doesn't match
+    test.send(close_mention_ix(&alice.pubkey(), &mention, &alice.pubkey()), &[&alice])
+        .assert_error(TwitterError::Unauthorized);
+
+    let mention_lamports = test.balance(&mention);
+    let alice_before = test.balance(&alice.pubkey());
+    test.send(close_mention_ix(&bob.pubkey(), &mention, &alice.pubkey()), &[&bob]).assert_ok();
+
+    assert!(!test.exists(&mention));
+    assert_eq!(test.balance(&alice.pubkey()), alice_before + mention_lamports);
+}
+
+/// Account data of a tweet created before tweets were seeded by profile index
+fn legacy_tweet_data(author: &Pubkey, topic: &str, content: &str) -> Vec<u8> {
+    let legacy = LegacyTweet {
+        tweet_author: *author,
+        topic: topic.to_string(),
+        content: content.to_string(),
+        likes: 3,
+        dislikes: 1,
+        bump: 0,
+    };
+    let mut data = Tweet::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn migrate_tweet_moves_legacy_tweet_and_refunds_rent() {
+    let (mut test, alice) = setup();
+    let topic = "Old topic";
+    let legacy = legacy_tweet_pda(topic, &alice.pubkey());
+    test.set_raw_account(&legacy, legacy_tweet_data(&alice.pubkey(), topic, "Old content"), 200);
+
+    let legacy_lamports = test.balance(&legacy);
+    let alice_before = test.balance(&alice.pubkey());
+
+    test.send(migrate_tweet_ix(&alice.pubkey(), 0, topic), &[&alice]).assert_ok();
+
+    let tweet = tweet_pda(&alice.pubkey(), 0);
+    let state = test.account::<Tweet>(&tweet);
+    assert_eq!(state.tweet_author, alice.pubkey());
+    assert_eq!(state.topic, topic);
+    assert_eq!(state.content, "Old content");
+    // The legacy likes and dislikes have no reaction accounts at the new address
+    assert_eq!(state.reaction_counts, [0; ReactionType::COUNT]);
+    assert_eq!(state.index, 0);
+
+    assert!(!test.exists(&legacy));
+    let tweet_lamports = test.balance(&tweet);
+    assert_eq!(
+        test.balance(&alice.pubkey()),
+        alice_before + legacy_lamports - tweet_lamports
+    );
+    assert_eq!(
+        test.account::<Profile>(&profile_pda(&alice.pubkey())).tweet_count,
+        1
+    );
+}
+
+#[test]
+fn migrate_tweet_rejects_tweet_of_another_author() {
+    let (mut test, alice) = setup();
+    let topic = "Old topic";
+    // Stored at alice's legacy address but claiming a different author
+    let legacy = legacy_tweet_pda(topic, &alice.pubkey());
+    test.set_raw_account(&legacy, legacy_tweet_data(&Pubkey::new_unique(), topic, "Old content"), 200);
+
+    let outcome = test.send(migrate_tweet_ix(&alice.pubkey(), 0, topic), &[&alice]);
+
+    outcome.assert_error(TwitterError::Unauthorized);
+    assert!(test.exists(&legacy));
+}
@@ -9,6 +9,51 @@ import crypto from "crypto";
 const TWEET_SEED = "TWEET_SEED";
 const TWEET_REACTION = "TWEET_REACTION_SEED";
 const COMMENT_SEED = "COMMENT_SEED";
+const PROFILE_SEED = "PROFILE_SEED";
+const HANDLE_SEED = "HANDLE_SEED";
+const FOLLOW_SEED = "FOLLOW_SEED";
+const MODERATION_SEED = "MODERATION_SEED";
+const BAN_SEED = "BAN_SEED";
+const TREASURY_SEED = "TREASURY_SEED";
+const RETWEET_SEED = "RETWEET_SEED";
+const BOOKMARK_SEED = "BOOKMARK_SEED";
+const POLL_SEED = "POLL_SEED";
+const POLL_VOTE_SEED = "POLL_VOTE_SEED";
+const TAG_SEED = "TAG_SEED";
+const MENTION_SEED = "MENTION_SEED";
+
+const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGFPxZ6HDaJXpkmcfMEW7p4ZWTAqRn");
+
+// Tweets are seeded by their author's profile counter, these are the indices
+// the tests below create them at
+const BOB_TWEET1 = 0;
+const BOB_TWEET_EDGE_TOPIC = 1;
+const BOB_TWEET_EDGE_CONTENT = 2;
+const BOB_TWEET_SINGLE_CHAR = 3;
+const BOB_TWEET_UNICODE = 4;
+const BOB_TWEET1_AGAIN = 5;
+const BOB_TWEET4 = 6;
+const CHARLIE_TWEET1 = 0;
+const CHARLIE_TWEET_EDIT = 1;
+const CHARLIE_TWEET_UNICODE = 2;
+const CHARLIE_TWEET_REAP = 4;
+const CHARLIE_TWEET_POLL = 5;
+const CHARLIE_TWEET_SHORT_POLL = 6;
+const CHARLIE_TWEET_SCHEDULED = 7;
+const CHARLIE_TWEET_EXPIRING = 8;
+const ALICE_TWEET_SWITCH = 0;
+const ALICE_TWEET_REACTIONS = 1;
+const ALICE_TWEET_EVENTS = 2;
+const ALICE_TWEET_GATED = 3;
+const ALICE_TWEET_TAGS = 4;
+const NON_EXISTENT_TWEET = 1000;
+const NON_EXISTENT_COMMENT = 1000;
+
+// TweetOptions of a tweet without a gate, poll or schedule
+const PLAIN_TWEET = { gate: null, poll: null, schedule: null };
+
+// Position of each ReactionType counter in Tweet.reactionCounts
+const REACTION_INDEX = { like: 0, dislike: 1, love: 2, laugh: 3, boost: 4, wow: 5, sad: 6, angry: 7 };
 
 describe("twitter", () => {
   const provider = anchor.AnchorProvider.env();
@@ -43,20 +88,131 @@ describe("twitter", () => {
   const content_edge_case = "B".repeat(500);
   const empty_topic = "";
   const empty_content = "";
+  const whitespace_content = " \t\n ";
   const single_char_topic = "X";
   const single_char_content = "Y";
   const unicode_topic = "🚀 Crypto";
   const unicode_content = "Testing with emojis 🎉✨🔥";
 
+  // Comments are addressed by their tweet's counter, so remember the ones later tests need
+  let alice_comment_pkey: PublicKey;
+  let alice_unicode_comment_pkey: PublicKey;
+
+  describe("Create Profile", async () => {
+    const handles = new Map([[bob, "bob"], [alice, "alice"], [charlie, "charlie_42"]]);
+
+    it("Should create a profile with a unique handle for each user", async () => {
+      for (const [user, handle] of handles) {
+        await airdrop(provider.connection, user.publicKey);
+        const [profile_pkey, profile_bump] = getProfileAddress(user.publicKey, program.programId);
+        const [handle_pkey, handle_bump] = getHandleAddress(handle, program.programId);
+
+        await program.methods.createProfile(handle, `${handle} on chain`, "Testing the twitter program").accounts(
+          {
+            authority: user.publicKey,
+            profile: profile_pkey,
+            handleRegistry: handle_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([user]).rpc({ commitment: "confirmed" })
+
+        const profileData = await program.account.profile.fetch(profile_pkey);
+        assert.strictEqual(profileData.authority.toString(), user.publicKey.toString(), "Profile authority should be the signer");
+        assert.strictEqual(profileData.handle, handle, "Profile handle should match");
+        assert.strictEqual(profileData.displayName, `${handle} on chain`, "Profile display name should match");
+        assert.strictEqual(profileData.bio, "Testing the twitter program", "Profile bio should match");
+        assert.strictEqual(profileData.tweetCount.toNumber(), 0, "New profile should have no tweets");
+        assert.strictEqual(profileData.followerCount.toNumber(), 0, "New profile should have no followers");
+        assert.strictEqual(profileData.followingCount.toNumber(), 0, "New profile should follow nobody");
+        assert.strictEqual(profileData.bump, profile_bump, "Profile bump should match");
+
+        const registryData = await program.account.handleRegistry.fetch(handle_pkey);
+        assert.strictEqual(registryData.profile.toString(), profile_pkey.toString(), "Handle should point at the profile");
+      }
+    });
+
+    it("Should fail to create a second profile for the same user", async () => {
+      const [profile_pkey, profile_bump] = getProfileAddress(bob.publicKey, program.programId);
+      const [handle_pkey, handle_bump] = getHandleAddress("bob_again", program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.createProfile("bob_again", "Bob", "").accounts(
+          {
+            authority: bob.publicKey,
+            profile: profile_pkey,
+            handleRegistry: handle_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error for a duplicate profile")
+      }
+      assert.strictEqual(should_fail, "Failed", "Profile creation should have failed for an existing profile")
+    });
+
+    it("Should fail to claim a handle that is already taken", async () => {
+      const dave = anchor.web3.Keypair.generate();
+      await airdrop(provider.connection, dave.publicKey);
+      const [profile_pkey, profile_bump] = getProfileAddress(dave.publicKey, program.programId);
+      const [handle_pkey, handle_bump] = getHandleAddress("bob", program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.createProfile("bob", "Not Bob", "").accounts(
+          {
+            authority: dave.publicKey,
+            profile: profile_pkey,
+            handleRegistry: handle_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([dave]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error for a taken handle")
+      }
+      assert.strictEqual(should_fail, "Failed", "Profile creation should have failed with a taken handle")
+    });
+
+    it("Should fail to create a profile with an invalid handle", async () => {
+      const dave = anchor.web3.Keypair.generate();
+      await airdrop(provider.connection, dave.publicKey);
+      const [profile_pkey, profile_bump] = getProfileAddress(dave.publicKey, program.programId);
+      const [handle_pkey, handle_bump] = getHandleAddress("Dave!", program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.createProfile("Dave!", "Dave", "").accounts(
+          {
+            authority: dave.publicKey,
+            profile: profile_pkey,
+            handleRegistry: handle_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([dave]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidHandle", "Expected 'InvalidHandle' error for a handle with uppercase letters and punctuation");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Profile creation should have failed with an invalid handle")
+    });
+  });
+
   describe("Initialize Tweet", async () => {
     it("Should successfully initialize a tweet with valid topic and content", async () => {
       await airdrop(provider.connection, bob.publicKey);
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
 
-      await program.methods.initialize(topic_bob1, content_bob1).accounts(
+      await program.methods.initialize(topic_bob1, content_bob1, PLAIN_TWEET).accounts(
         {
           tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -66,13 +222,17 @@ describe("twitter", () => {
       )
     });
 
-    it("Should successfully initialize tweet with exactly 32-byte topic (boundary test)", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_edge_case, bob.publicKey, program.programId);
+    it("Should successfully initialize tweet with exactly 32-character topic (boundary test)", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET_EDGE_TOPIC, program.programId);
 
-      await program.methods.initialize(topic_edge_case, content_bob1).accounts(
+      await program.methods.initialize(topic_edge_case, content_bob1, PLAIN_TWEET).accounts(
         {
           tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -82,13 +242,17 @@ describe("twitter", () => {
       )
     });
 
-    it("Should successfully initialize tweet with exactly 500-byte content (boundary test)", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress("EdgeContent", bob.publicKey, program.programId);
+    it("Should successfully initialize tweet with exactly 500-character content (boundary test)", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET_EDGE_CONTENT, program.programId);
 
-      await program.methods.initialize("EdgeContent", content_edge_case).accounts(
+      await program.methods.initialize("EdgeContent", content_edge_case, PLAIN_TWEET).accounts(
         {
           tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -98,29 +262,68 @@ describe("twitter", () => {
       )
     });
 
-    it("Should successfully initialize tweet with empty content", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress("EmptyContent", bob.publicKey, program.programId);
-
-      await program.methods.initialize("EmptyContent", empty_content).accounts(
-        {
-          tweetAuthority: bob.publicKey,
-          tweet: tweet_pkey,
-          systemProgram: anchor.web3.SystemProgram.programId
+    it("Should fail to initialize tweet with empty or whitespace-only content", async () => {
+      // Failed attempts don't use up the profile counter, so this is still the next index
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET_SINGLE_CHAR, program.programId);
+
+      for (const content of [empty_content, whitespace_content]) {
+        let should_fail = "This Should Fail"
+        try {
+          await program.methods.initialize("EmptyContent", content, PLAIN_TWEET).accounts(
+            {
+              tweetAuthority: bob.publicKey,
+              profile: getProfileAddress(bob.publicKey, program.programId)[0],
+              tweet: tweet_pkey,
+              ban: getBanAddress(bob.publicKey, program.programId)[0],
+              gateMint: null,
+              poll: null,
+              systemProgram: anchor.web3.SystemProgram.programId
+            }
+          ).signers([bob]).rpc({ commitment: "confirmed" })
+        } catch (error) {
+          const err = anchor.AnchorError.parse(error.logs);
+          assert.strictEqual(err.error.errorCode.code, "ContentEmpty", "Expected 'ContentEmpty' error for blank content");
+          should_fail = "Failed"
         }
-      ).signers([bob]).rpc({ commitment: "confirmed" })
+        assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed with blank content")
+      }
+    });
 
-      await checkTweet(
-        program, tweet_pkey, bob.publicKey, "EmptyContent", empty_content, 0, 0, tweet_bump
-      )
+    it("Should fail to initialize tweet with an empty topic", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET_SINGLE_CHAR, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initialize(empty_topic, content_bob1, PLAIN_TWEET).accounts(
+          {
+            tweetAuthority: bob.publicKey,
+            profile: getProfileAddress(bob.publicKey, program.programId)[0],
+            tweet: tweet_pkey,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TopicEmpty", "Expected 'TopicEmpty' error for an empty topic");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed with an empty topic")
     });
 
     it("Should successfully initialize tweet with single character topic and content", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(single_char_topic, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET_SINGLE_CHAR, program.programId);
 
-      await program.methods.initialize(single_char_topic, single_char_content).accounts(
+      await program.methods.initialize(single_char_topic, single_char_content, PLAIN_TWEET).accounts(
         {
           tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -131,12 +334,16 @@ describe("twitter", () => {
     });
 
     it("Should successfully initialize tweet with unicode characters and emojis", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(unicode_topic, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET_UNICODE, program.programId);
 
-      await program.methods.initialize(unicode_topic, unicode_content).accounts(
+      await program.methods.initialize(unicode_topic, unicode_content, PLAIN_TWEET).accounts(
         {
           tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -146,72 +353,111 @@ describe("twitter", () => {
       )
     });
 
-    it("Should fail to initialize tweet when topic exceeds 32 bytes", async () => {
+    it("Should fail to initialize tweet when topic exceeds 32 characters", async () => {
 
       let should_fail = "This Should Fail"
       try {
-        const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob2, bob.publicKey, program.programId);
+        const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1_AGAIN, program.programId);
 
-        await program.methods.initialize(topic_bob2, content_bob2).accounts(
+        await program.methods.initialize(topic_bob2, content_bob2, PLAIN_TWEET).accounts(
           {
             tweetAuthority: bob.publicKey,
+            profile: getProfileAddress(bob.publicKey, program.programId)[0],
             tweet: tweet_pkey,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([bob]).rpc({ commitment: "confirmed" })
       } catch (error) {
-        assert.strictEqual(error.message, "Max seed length exceeded", "Expected 'Max seed length exceeded' error for topic longer than 32 bytes");
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TopicTooLong", "Expected 'TopicTooLong' error for topic longer than 32 characters");
         should_fail = "Failed"
       }
-      assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed with topic longer than 32 bytes")
+      assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed with topic longer than 32 characters")
     });
 
-    it("Should fail to initialize tweet when content exceeds 500 bytes", async () => {
+    it("Should fail to initialize tweet when content exceeds 500 characters", async () => {
       let should_fail = "This Should Fail"
       try {
-        const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob3, bob.publicKey, program.programId);
+        const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1_AGAIN, program.programId);
 
-        await program.methods.initialize(topic_bob3, content_bob3).accounts(
+        await program.methods.initialize(topic_bob3, content_bob3, PLAIN_TWEET).accounts(
           {
             tweetAuthority: bob.publicKey,
+            profile: getProfileAddress(bob.publicKey, program.programId)[0],
             tweet: tweet_pkey,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([bob]).rpc({ commitment: "confirmed" })
       } catch (error) {
         const err = anchor.AnchorError.parse(error.logs);
-        assert.strictEqual(err.error.errorCode.code, "ContentTooLong", "Expected 'ContentTooLong' error for content longer than 500 bytes");
+        assert.strictEqual(err.error.errorCode.code, "ContentTooLong", "Expected 'ContentTooLong' error for content longer than 500 characters");
         should_fail = "Failed"
       }
-      assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed with content longer than 500 bytes")
+      assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed with content longer than 500 characters")
+    });
+
+    it("Should allow the same author to post another tweet with the same topic", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1_AGAIN, program.programId);
+
+      await program.methods.initialize(topic_bob1, "Different content", PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(
+        program, tweet_pkey, bob.publicKey, topic_bob1, "Different content", 0, 0, tweet_bump
+      )
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.index.toNumber(), BOB_TWEET1_AGAIN, "Tweet should store its profile index");
     });
 
-    it("Should fail to initialize duplicate tweet with same topic and author", async () => {
+    it("Should fail to initialize a tweet at an index that is already taken", async () => {
       let should_fail = "This Should Fail"
       try {
-        const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+        const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
 
-        await program.methods.initialize(topic_bob1, "Different content").accounts(
+        await program.methods.initialize(topic_bob1, "Overwrite attempt", PLAIN_TWEET).accounts(
           {
             tweetAuthority: bob.publicKey,
+            profile: getProfileAddress(bob.publicKey, program.programId)[0],
             tweet: tweet_pkey,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([bob]).rpc({ commitment: "confirmed" })
       } catch (error) {
         should_fail = "Failed"
-        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error for duplicate tweet with same topic and author")
+        assert.isTrue(error.message.includes("seeds") || SolanaError.contains(error.logs, "ConstraintSeeds"), "Expected seeds constraint error when reusing an old tweet index")
       }
-      assert.strictEqual(should_fail, "Failed", "Tweet initialization should have failed when trying to create duplicate tweet with same topic and author")
+      assert.strictEqual(should_fail, "Failed", "Tweet initialization should only accept the profile's next index")
     });
 
     it("Should successfully initialize second tweet with different topic for same author", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
 
-      await program.methods.initialize(topic_bob4, content_bob4).accounts(
+      await program.methods.initialize(topic_bob4, content_bob4, PLAIN_TWEET).accounts(
         {
           tweetAuthority: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -222,12 +468,16 @@ describe("twitter", () => {
 
     it("Should allow different users to create tweets with same topic", async () => {
       await airdrop(provider.connection, charlie.publicKey);
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, charlie.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
 
-      await program.methods.initialize(topic_bob1, "Charlie's version").accounts(
+      await program.methods.initialize(topic_bob1, "Charlie's version", PLAIN_TWEET).accounts(
         {
           tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
           tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([charlie]).rpc({ commitment: "confirmed" })
@@ -242,7 +492,7 @@ describe("twitter", () => {
     it("Should successfully add like reaction to tweet", async () => {
       await airdrop(provider.connection, alice.publicKey);
 
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       await program.methods.likeTweet().accounts(
@@ -250,6 +500,8 @@ describe("twitter", () => {
           reactionAuthor: alice.publicKey,
           tweetReaction: reaction_pkey,
           tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -262,7 +514,7 @@ describe("twitter", () => {
     });
 
     it("Should correctly set reaction type to 'like' enum variant", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       let reactionData = await program.account.reaction.fetch(reaction_pkey);
@@ -271,7 +523,7 @@ describe("twitter", () => {
 
     it("Should fail when attempting to like the same tweet twice", async () => {
 
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       let should_fail = "This should fail";
@@ -281,6 +533,8 @@ describe("twitter", () => {
             reactionAuthor: alice.publicKey,
             tweetReaction: reaction_pkey,
             tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -296,7 +550,7 @@ describe("twitter", () => {
 
     it("Should fail when attempting to dislike a tweet that is already liked", async () => {
 
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       let should_fail = "This should fail";
@@ -306,6 +560,8 @@ describe("twitter", () => {
             reactionAuthor: alice.publicKey,
             tweetReaction: reaction_pkey,
             tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -321,7 +577,7 @@ describe("twitter", () => {
     });
 
     it("Should successfully add dislike reaction to different tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       await program.methods.dislikeTweet().accounts(
@@ -329,6 +585,8 @@ describe("twitter", () => {
           reactionAuthor: alice.publicKey,
           tweetReaction: reaction_pkey,
           tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -342,7 +600,7 @@ describe("twitter", () => {
     });
 
     it("Should correctly set reaction type to 'dislike' enum variant", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       let reactionData = await program.account.reaction.fetch(reaction_pkey);
@@ -351,7 +609,7 @@ describe("twitter", () => {
 
     it("Should fail when attempting to dislike the same tweet twice", async () => {
 
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       let should_fail = "This should fail";
@@ -361,6 +619,8 @@ describe("twitter", () => {
             reactionAuthor: alice.publicKey,
             tweetReaction: reaction_pkey,
             tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -380,6 +640,8 @@ describe("twitter", () => {
             reactionAuthor: alice.publicKey,
             tweetReaction: reaction_pkey,
             tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -394,7 +656,7 @@ describe("twitter", () => {
     });
 
     it("Should allow multiple users to react to the same tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
 
       await program.methods.likeTweet().accounts(
@@ -402,6 +664,8 @@ describe("twitter", () => {
           reactionAuthor: charlie.publicKey,
           tweetReaction: reaction_pkey,
           tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([charlie]).rpc({ commitment: "confirmed" })
@@ -416,7 +680,7 @@ describe("twitter", () => {
     });
 
     it("Should fail when attempting to react to non-existent tweet", async () => {
-      const [fake_tweet_pkey, fake_tweet_bump] = getTweetAddress("NonExistent", bob.publicKey, program.programId);
+      const [fake_tweet_pkey, fake_tweet_bump] = getTweetAddress(bob.publicKey, NON_EXISTENT_TWEET, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, fake_tweet_pkey, program.programId);
 
       let should_fail = "This should fail";
@@ -426,6 +690,8 @@ describe("twitter", () => {
             reactionAuthor: alice.publicKey,
             tweetReaction: reaction_pkey,
             tweet: fake_tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -439,7 +705,7 @@ describe("twitter", () => {
 
   describe("Remove Reaction", async () => {
     it("Should successfully remove existing reaction from tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       await program.methods.reactionRemove().accounts(
@@ -456,7 +722,7 @@ describe("twitter", () => {
     });
 
     it("Should properly delete reaction account after removal", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       let should_fail = "This should fail"
@@ -471,7 +737,7 @@ describe("twitter", () => {
 
     it("Should allow adding new reaction after previous reaction was removed", async () => {
 
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
       await program.methods.likeTweet().accounts(
@@ -479,6 +745,8 @@ describe("twitter", () => {
           reactionAuthor: alice.publicKey,
           tweetReaction: reaction_pkey,
           tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -492,7 +760,7 @@ describe("twitter", () => {
     });
 
     it("Should fail when attempting to remove non-existent reaction", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, charlie.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
 
       let should_fail = "This should fail";
@@ -512,7 +780,7 @@ describe("twitter", () => {
     });
 
     it("Should fail when attempting to remove another user's reaction", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
 
       let should_fail = "This should fail";
@@ -536,8 +804,8 @@ describe("twitter", () => {
   describe("Add Comment", async () => {
     it("Should fail when attempting to add comment exceeding length limit", async () => {
 
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
-      const [comment_pkey, comment_bump] = getCommentAddress(comment_alice1, alice.publicKey, tweet_pkey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
       let should_fail = "This Should Fail"
       try {
@@ -546,45 +814,58 @@ describe("twitter", () => {
             commentAuthor: alice.publicKey,
             comment: comment_pkey,
             tweet: tweet_pkey,
+            parentComment: null,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
       } catch (error) {
         const err = anchor.AnchorError.parse(error.logs);
-        assert.strictEqual(err.error.errorCode.code, "CommentTooLong", "Expected 'CommentTooLong' error for comment longer than 500 bytes");
+        assert.strictEqual(err.error.errorCode.code, "CommentTooLong", "Expected 'CommentTooLong' error for comment longer than 500 characters");
         should_fail = "Failed"
       }
-      assert.strictEqual(should_fail, "Failed", "Comment creation should have failed with comment longer than 500 bytes")
+      assert.strictEqual(should_fail, "Failed", "Comment creation should have failed with comment longer than 500 characters")
 
     });
 
     it("Should successfully add comment with valid length to tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
-      const [comment_pkey, comment_bump] = getCommentAddress(comment_alice2, alice.publicKey, tweet_pkey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
       await program.methods.commentTweet(comment_alice2).accounts(
         {
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
       await checkComment(
         program, comment_pkey, alice.publicKey, tweet_pkey, comment_alice2, comment_bump
       )
+      alice_comment_pkey = comment_pkey;
     });
 
     it("Should successfully add comment with exactly 500 characters (boundary test)", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const max_comment = "C".repeat(500);
-      const [comment_pkey, comment_bump] = getCommentAddress(max_comment, alice.publicKey, tweet_pkey, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
       await program.methods.commentTweet(max_comment).accounts(
         {
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -594,35 +875,48 @@ describe("twitter", () => {
       )
     });
 
-    it("Should successfully add empty comment to tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
-      const empty_comment = "";
-      const [comment_pkey, comment_bump] = getCommentAddress(empty_comment, alice.publicKey, tweet_pkey, program.programId);
-
-      await program.methods.commentTweet(empty_comment).accounts(
-        {
-          commentAuthor: alice.publicKey,
-          comment: comment_pkey,
-          tweet: tweet_pkey,
-          systemProgram: anchor.web3.SystemProgram.programId
+    it("Should fail to add an empty or whitespace-only comment", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+
+      for (const comment of ["", whitespace_content]) {
+        let should_fail = "This Should Fail"
+        try {
+          await program.methods.commentTweet(comment).accounts(
+            {
+              commentAuthor: alice.publicKey,
+              comment: comment_pkey,
+              tweet: tweet_pkey,
+              parentComment: null,
+              ban: getBanAddress(alice.publicKey, program.programId)[0],
+              moderationConfig: getModerationAddress(program.programId)[0],
+              gateTokenAccount: null,
+              systemProgram: anchor.web3.SystemProgram.programId
+            }
+          ).signers([alice]).rpc({ commitment: "confirmed" })
+        } catch (error) {
+          const err = anchor.AnchorError.parse(error.logs);
+          assert.strictEqual(err.error.errorCode.code, "CommentEmpty", "Expected 'CommentEmpty' error for a blank comment");
+          should_fail = "Failed"
         }
-      ).signers([alice]).rpc({ commitment: "confirmed" })
-
-      await checkComment(
-        program, comment_pkey, alice.publicKey, tweet_pkey, empty_comment, comment_bump
-      )
+        assert.strictEqual(should_fail, "Failed", "Adding a blank comment should have failed")
+      }
     });
 
     it("Should successfully add comment with unicode characters and emojis", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const unicode_comment = "Great tweet! 🎉✨ Love it! 💯";
-      const [comment_pkey, comment_bump] = getCommentAddress(unicode_comment, alice.publicKey, tweet_pkey, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
       await program.methods.commentTweet(unicode_comment).accounts(
         {
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -630,18 +924,23 @@ describe("twitter", () => {
       await checkComment(
         program, comment_pkey, alice.publicKey, tweet_pkey, unicode_comment, comment_bump
       )
+      alice_unicode_comment_pkey = comment_pkey;
     });
 
     it("Should allow multiple users to comment on the same tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const charlie_comment = "Charlie's comment here";
-      const [comment_pkey, comment_bump] = getCommentAddress(charlie_comment, charlie.publicKey, tweet_pkey, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
       await program.methods.commentTweet(charlie_comment).accounts(
         {
           commentAuthor: charlie.publicKey,
           comment: comment_pkey,
           tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([charlie]).rpc({ commitment: "confirmed" })
@@ -652,9 +951,9 @@ describe("twitter", () => {
     });
 
     it("Should fail when attempting to comment on non-existent tweet", async () => {
-      const [fake_tweet_pkey, fake_tweet_bump] = getTweetAddress("FakeTweet", bob.publicKey, program.programId);
+      const [fake_tweet_pkey, fake_tweet_bump] = getTweetAddress(bob.publicKey, NON_EXISTENT_TWEET, program.programId);
       const test_comment = "This should fail";
-      const [comment_pkey, comment_bump] = getCommentAddress(test_comment, alice.publicKey, fake_tweet_pkey, program.programId);
+      const [comment_pkey, comment_bump] = getCommentAddress(fake_tweet_pkey, 0, program.programId);
 
       let should_fail = "This should fail";
       try {
@@ -663,6 +962,10 @@ describe("twitter", () => {
             commentAuthor: alice.publicKey,
             comment: comment_pkey,
             tweet: fake_tweet_pkey,
+            parentComment: null,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
             systemProgram: anchor.web3.SystemProgram.programId
           }
         ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -673,32 +976,46 @@ describe("twitter", () => {
       assert.strictEqual(should_fail, "Failed", "Should not be able to comment on a non-existent tweet");
     });
 
-    it("Should fail when attempting to create duplicate comment with same content", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
-      const [comment_pkey, comment_bump] = getCommentAddress(comment_alice2, alice.publicKey, tweet_pkey, program.programId);
+    it("Should allow the same user to post a comment with the same content again", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
-      let should_fail = "This should fail";
-      try {
-        await program.methods.commentTweet(comment_alice2).accounts(
-          {
-            commentAuthor: alice.publicKey,
-            comment: comment_pkey,
-            tweet: tweet_pkey,
-            systemProgram: anchor.web3.SystemProgram.programId
-          }
-        ).signers([alice]).rpc({ commitment: "confirmed" })
-      } catch (error) {
-        should_fail = "Failed"
-        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error when trying to create duplicate comment")
-      }
-      assert.strictEqual(should_fail, "Failed", "Should not be able to create duplicate comment with same content from same user");
+      await program.methods.commentTweet(comment_alice2).accounts(
+        {
+          commentAuthor: alice.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      await checkComment(
+        program, comment_pkey, alice.publicKey, tweet_pkey, comment_alice2, comment_bump
+      )
+      assert.notStrictEqual(comment_pkey.toString(), alice_comment_pkey.toString(), "Duplicate text should get its own comment account");
+    });
+
+    it("Should give comments sequential ids per tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const first = await program.account.comment.fetch(alice_comment_pkey);
+      const [second_pkey, second_bump] = getCommentAddress(tweet_pkey, first.id.toNumber() + 1, program.programId);
+      const second = await program.account.comment.fetch(second_pkey);
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+
+      assert.strictEqual(first.id.toNumber(), 0, "First comment on the tweet should have id 0");
+      assert.strictEqual(second.content, comment_alice2, "Next id should hold the duplicate comment");
+      assert.strictEqual(tweetData.commentCount.toNumber(), 2, "Tweet should count both comments");
     });
   });
 
   describe("Remove Comment", async () => {
     it("Should successfully remove existing comment from tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
-      const [comment_pkey, comment_bump] = getCommentAddress(comment_alice2, alice.publicKey, tweet_pkey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const comment_pkey = alice_comment_pkey;
 
       await program.methods.commentRemove().accounts(
         {
@@ -719,9 +1036,9 @@ describe("twitter", () => {
     });
 
     it("Should fail when attempting to remove non-existent comment", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const fake_comment = "This comment doesn't exist";
-      const [comment_pkey, comment_bump] = getCommentAddress(fake_comment, alice.publicKey, tweet_pkey, program.programId);
+      const [comment_pkey, comment_bump] = getCommentAddress(tweet_pkey, NON_EXISTENT_COMMENT, program.programId);
 
       let should_fail = "This should fail";
       try {
@@ -739,9 +1056,9 @@ describe("twitter", () => {
     });
 
     it("Should fail when attempting to remove another user's comment", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const unicode_comment = "Great tweet! 🎉✨ Love it! 💯";
-      const [comment_pkey, comment_bump] = getCommentAddress(unicode_comment, alice.publicKey, tweet_pkey, program.programId);
+      const comment_pkey = alice_unicode_comment_pkey;
 
       let should_fail = "This should fail";
       try {
@@ -753,21 +1070,26 @@ describe("twitter", () => {
         ).signers([charlie]).rpc({ commitment: "confirmed" })
       } catch (error) {
         should_fail = "Failed"
-        assert.isTrue(error.message.includes("constraint") || error.message.includes("seeds"), "Expected constraint or seeds error when trying to remove someone else's comment")
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when trying to remove someone else's comment");
       }
       assert.strictEqual(should_fail, "Failed", "Should not be able to remove someone else's comment (authorization check)");
     });
 
-    it("Should allow recreating comment with same content after deletion", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
-      const [comment_pkey, comment_bump] = getCommentAddress(comment_alice2, alice.publicKey, tweet_pkey, program.programId);
+    it("Should allow posting the same content again after deletion", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
-      // Recreate the same comment that was deleted
+      // Post the same text as the deleted comment, it gets a fresh id
       await program.methods.commentTweet(comment_alice2).accounts(
         {
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([alice]).rpc({ commitment: "confirmed" })
@@ -780,7 +1102,7 @@ describe("twitter", () => {
 
   describe("Edge Cases and Error Handling", async () => {
     it("Should allow tweet author to react to their own tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
 
       await program.methods.dislikeTweet().accounts(
@@ -788,6 +1110,8 @@ describe("twitter", () => {
           reactionAuthor: bob.publicKey,
           tweetReaction: reaction_pkey,
           tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -799,15 +1123,19 @@ describe("twitter", () => {
     });
 
     it("Should allow tweet author to comment on their own tweet", async () => {
-      const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       const bob_comment = "Thanks for the likes everyone!";
-      const [comment_pkey, comment_bump] = getCommentAddress(bob_comment, bob.publicKey, tweet_pkey, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
       await program.methods.commentTweet(bob_comment).accounts(
         {
           commentAuthor: bob.publicKey,
           comment: comment_pkey,
           tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId
         }
       ).signers([bob]).rpc({ commitment: "confirmed" })
@@ -819,46 +1147,2704 @@ describe("twitter", () => {
 
     it("Should maintain correct final state across all tweets and reactions", async () => {
       // Bob's first tweet should have: 2 likes, 1 dislike
-      const [tweet1_pkey, tweet1_bump] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [tweet1_pkey, tweet1_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
       await checkTweet(program, tweet1_pkey, bob.publicKey, topic_bob1, content_bob1, 2, 1, tweet1_bump);
 
       // Bob's second tweet should have: 1 like, 0 dislikes
-      const [tweet2_pkey, tweet2_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
+      const [tweet2_pkey, tweet2_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
       await checkTweet(program, tweet2_pkey, bob.publicKey, topic_bob4, content_bob4, 1, 0, tweet2_bump);
 
       // Charlie's tweet should have: 0 likes, 0 dislikes
-      const [tweet3_pkey, tweet3_bump] = getTweetAddress(topic_bob1, charlie.publicKey, program.programId);
+      const [tweet3_pkey, tweet3_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
       await checkTweet(program, tweet3_pkey, charlie.publicKey, topic_bob1, "Charlie's version", 0, 0, tweet3_bump);
     });
   });
 
-});
+  describe("Threaded Replies", async () => {
+    const root_comment = "Root of the thread";
+    let root_comment_pkey: PublicKey;
+    let reply_comment_pkey: PublicKey;
 
+    it("Should create top-level comments at depth 0 without a parent", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
-async function airdrop(connection: any, address: any, amount = 1000000000) {
-  await connection.confirmTransaction(await connection.requestAirdrop(address, amount), "confirmed");
-}
+      await program.methods.commentTweet(root_comment).accounts(
+        {
+          commentAuthor: charlie.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
 
-function getCommentAddress(comment_content: string, author: PublicKey, parent_tweet: PublicKey, programID: PublicKey) {
-  let hexString = crypto.createHash('sha256').update(comment_content, 'utf-8').digest('hex');
-  let content_seed = Uint8Array.from(Buffer.from(hexString, 'hex'));
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.isNull(commentData.parentComment, "Top-level comment should have no parent comment");
+      assert.strictEqual(commentData.depth, 0, "Top-level comment should be at depth 0");
+      root_comment_pkey = comment_pkey;
+    });
 
+    it("Should reply to a comment on the same tweet one level deeper", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const parent_pkey = root_comment_pkey;
+      const reply = "Replying to Charlie";
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
 
-  return PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode(COMMENT_SEED),
-      author.toBuffer(),
-      content_seed,
-      parent_tweet.toBuffer(),
-    ], programID);
-}
+      await program.methods.commentTweet(reply).accounts(
+        {
+          commentAuthor: bob.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: parent_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
 
-function getTweetAddress(topic: string, author: PublicKey, programID: PublicKey) {
-  return PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode(topic),
-      anchor.utils.bytes.utf8.encode(TWEET_SEED),
-      author.toBuffer()
+      await checkComment(program, comment_pkey, bob.publicKey, tweet_pkey, reply, comment_bump)
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(commentData.parentComment.toString(), parent_pkey.toString(), "Reply should point at its parent comment");
+      assert.strictEqual(commentData.depth, 1, "Reply should be one level below its parent");
+      reply_comment_pkey = comment_pkey;
+    });
+
+    it("Should fail when replying to a comment from a different tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [other_tweet_pkey, other_tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const parent_pkey = root_comment_pkey;
+      const reply = "Wrong thread";
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.commentTweet(reply).accounts(
+          {
+            commentAuthor: alice.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: parent_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ParentCommentMismatch", "Expected 'ParentCommentMismatch' error for a parent comment on another tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reply should have failed with a parent comment from a different tweet")
+    });
+
+    it("Should fail when a reply would exceed the maximum depth", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const MAX_COMMENT_DEPTH = 8;
+
+      // Extend the existing depth-1 reply down to the maximum depth
+      let parent_pkey = reply_comment_pkey;
+      for (let depth = 2; depth <= MAX_COMMENT_DEPTH; depth++) {
+        const reply = `Reply at depth ${depth}`;
+        const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+        await program.methods.commentTweet(reply).accounts(
+          {
+            commentAuthor: alice.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: parent_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+        parent_pkey = comment_pkey;
+      }
+
+      const reply = "One level too deep";
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.commentTweet(reply).accounts(
+          {
+            commentAuthor: alice.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: parent_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MaxCommentDepthReached", "Expected 'MaxCommentDepthReached' error for a reply below the maximum depth");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reply should have failed beyond the maximum comment depth")
+    });
+  });
+
+  describe("Change Reaction", async () => {
+    const topic_switch = "Switching sides";
+
+    it("Should switch a like to a dislike in place", async () => {
+      await airdrop(provider.connection, alice.publicKey);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_SWITCH, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.initialize(topic_switch, "Like it or not", PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: alice.publicKey,
+          profile: getProfileAddress(alice.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+      const lamports_before = await provider.connection.getBalance(reaction_pkey);
+
+      await program.methods.changeReaction({ dislike: {} }).accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(program, tweet_pkey, alice.publicKey, topic_switch, "Like it or not", 0, 1, tweet_bump)
+      const reactionData = await program.account.reaction.fetch(reaction_pkey);
+      assert.deepEqual(reactionData.reaction, { dislike: {} }, "Reaction should now be a dislike");
+      assert.strictEqual(await provider.connection.getBalance(reaction_pkey), lamports_before, "Reaction account should keep its rent");
+    });
+
+    it("Should switch a dislike back to a like", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_SWITCH, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.changeReaction({ like: {} }).accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(program, tweet_pkey, alice.publicKey, topic_switch, "Like it or not", 1, 0, tweet_bump)
+    });
+
+    it("Should fail when changing to the reaction type already set", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_SWITCH, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.changeReaction({ like: {} }).accounts(
+          {
+            reactionAuthor: charlie.publicKey,
+            tweetReaction: reaction_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "SameReaction", "Expected 'SameReaction' error when the type does not change");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Changing to the same reaction type should have failed")
+      await checkTweet(program, tweet_pkey, alice.publicKey, topic_switch, "Like it or not", 1, 0, tweet_bump)
+    });
+
+    it("Should fail when changing another user's reaction", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_SWITCH, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.changeReaction({ dislike: {} }).accounts(
+          {
+            reactionAuthor: bob.publicKey, // Bob trying to flip Charlie's reaction
+            tweetReaction: reaction_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+        assert.isTrue(error.message.includes("constraint") || error.message.includes("seeds"), "Expected constraint or seeds error when changing someone else's reaction")
+      }
+      assert.strictEqual(should_fail, "Failed", "Should not be able to change someone else's reaction")
+      await checkTweet(program, tweet_pkey, alice.publicKey, topic_switch, "Like it or not", 1, 0, tweet_bump)
+    });
+  });
+
+  describe("Extended Reactions", async () => {
+    const topic_reactions = "Reaction zoo";
+
+    it("Should count each reaction type in its own slot", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_REACTIONS, program.programId);
+
+      await program.methods.initialize(topic_reactions, "React however you like", PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: alice.publicKey,
+          profile: getProfileAddress(alice.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const reactions: [anchor.web3.Keypair, any][] = [[bob, { love: {} }], [charlie, { boost: {} }]];
+      for (const [user, reaction] of reactions) {
+        const [reaction_pkey, reaction_bump] = getReactionAddress(user.publicKey, tweet_pkey, program.programId);
+        await program.methods.reactTweet(reaction).accounts(
+          {
+            reactionAuthor: user.publicKey,
+            tweetReaction: reaction_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(user.publicKey, program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([user]).rpc({ commitment: "confirmed" })
+      }
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      const counts = tweetData.reactionCounts.map(count => count.toNumber());
+      assert.deepEqual(counts, [0, 0, 1, 0, 1, 0, 0, 0], "Love and boost should each have one reaction");
+
+      const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
+      const reactionData = await program.account.reaction.fetch(reaction_pkey);
+      assert.deepEqual(reactionData.reaction, { love: {} }, "Reaction type should be 'love'");
+    });
+
+    it("Should switch between extended reaction types", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_REACTIONS, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.changeReaction({ laugh: {} }).accounts(
+        {
+          reactionAuthor: bob.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.reactionCounts[REACTION_INDEX.love].toNumber(), 0, "Love count should drop back to zero");
+      assert.strictEqual(tweetData.reactionCounts[REACTION_INDEX.laugh].toNumber(), 1, "Laugh count should be one");
+    });
+
+    it("Should decrement the matching counter when an extended reaction is removed", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_REACTIONS, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.reactionRemove().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      const counts = tweetData.reactionCounts.map(count => count.toNumber());
+      assert.deepEqual(counts, [0, 0, 0, 1, 0, 0, 0, 0], "Only the laugh reaction should remain");
+    });
+  });
+
+  describe("Edit and Delete Tweet", async () => {
+    const topic_edit = "Second thoughts";
+    const original_content = "First draft of this tweet";
+    const edited_content = "Second draft, much better";
+
+    it("Should let the author edit a tweet and record the edit", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_EDIT, program.programId);
+
+      await program.methods.initialize(topic_edit, original_content, PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await program.methods.editTweet(edited_content).accounts(
+        {
+          tweetAuthor: charlie.publicKey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(program, tweet_pkey, charlie.publicKey, topic_edit, edited_content, 0, 0, tweet_bump)
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      const expected_hash = crypto.createHash('sha256').update(original_content, 'utf-8').digest('hex');
+      assert.strictEqual(tweetData.editCount, 1, "Edit count should be 1 after the first edit");
+      assert.isTrue(tweetData.lastEditedAt.toNumber() > 0, "Last edit timestamp should be set");
+      assert.strictEqual(Buffer.from(tweetData.previousContentHash).toString('hex'), expected_hash, "Previous content hash should match the original content");
+    });
+
+    it("Should fail when someone other than the author edits a tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_EDIT, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.editTweet("Alice was here").accounts(
+          {
+            tweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when editing someone else's tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the author should be able to edit a tweet")
+      await checkTweet(program, tweet_pkey, charlie.publicKey, topic_edit, edited_content)
+    });
+
+    it("Should fail to edit a tweet with content exceeding 500 characters", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_EDIT, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.editTweet(content_bob3).accounts(
+          {
+            tweetAuthor: charlie.publicKey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ContentTooLong", "Expected 'ContentTooLong' error for edited content longer than 500 characters");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Edit should have failed with content longer than 500 characters")
+    });
+
+    it("Should fail when someone other than the author deletes a tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_EDIT, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.deleteTweet().accounts(
+          {
+            tweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when deleting someone else's tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the author should be able to delete a tweet")
+    });
+
+    it("Should let the author delete a tweet and refund its rent", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_EDIT, program.programId);
+      const tweet_lamports = await provider.connection.getBalance(tweet_pkey);
+      const author_before = await provider.connection.getBalance(charlie.publicKey);
+
+      await program.methods.deleteTweet().accounts(
+        {
+          tweetAuthor: charlie.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const author_after = await provider.connection.getBalance(charlie.publicKey);
+      assert.isNull(await provider.connection.getAccountInfo(tweet_pkey), "Tweet account should be closed");
+      // The author also pays the transaction fee, so allow for it
+      assert.isTrue(author_after > author_before + tweet_lamports - 10_000, "Tweet rent should be refunded to the author");
+    });
+  });
+
+  describe("Profile Tweet Index", async () => {
+    it("Should count every tweet created through the profile", async () => {
+      const [profile_pkey, profile_bump] = getProfileAddress(bob.publicKey, program.programId);
+      const profileData = await program.account.profile.fetch(profile_pkey);
+      assert.strictEqual(profileData.tweetCount.toNumber(), BOB_TWEET4 + 1, "Bob's profile should count all of his tweets");
+    });
+
+    it("Should find tweets by topic with a memcmp filter", async () => {
+      // Strings are stored as a u32 length prefix followed by the bytes
+      const topic_bytes = Buffer.from(topic_bob1, "utf-8");
+      const length_prefix = new anchor.BN(topic_bytes.length).toArrayLike(Buffer, "le", 4);
+      const tweets = await program.account.tweet.all([
+        { memcmp: { offset: 8 + 32, bytes: anchor.utils.bytes.bs58.encode(Buffer.concat([length_prefix, topic_bytes])) } }
+      ]);
+
+      const found = tweets.map(tweet => tweet.publicKey.toString()).sort();
+      const expected = [
+        getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId)[0],
+        getTweetAddress(bob.publicKey, BOB_TWEET1_AGAIN, program.programId)[0],
+        getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId)[0],
+      ].map(key => key.toString()).sort();
+      assert.deepEqual(found, expected, "Topic filter should return every tweet with that topic");
+    });
+
+    it("Should fail to migrate a tweet that has no legacy account", async () => {
+      const [legacy_pkey, legacy_bump] = getLegacyTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [profile_pkey, profile_bump] = getProfileAddress(bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4 + 1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.migrateTweet(topic_bob1).accounts(
+          {
+            tweetAuthority: bob.publicKey,
+            profile: profile_pkey,
+            legacyTweet: legacy_pkey,
+            tweet: tweet_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidLegacyTweet", "Expected 'InvalidLegacyTweet' error for a missing legacy tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Migration should have failed without a legacy tweet")
+    });
+
+    it("Should fail to reap a legacy comment that has no legacy account", async () => {
+      const [legacy_pkey, legacy_bump] = getLegacyTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [comment_pkey, comment_bump] = getLegacyCommentAddress(alice.publicKey, comment_alice1, legacy_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.reapLegacyComment(comment_alice1).accounts(
+          {
+            reaper: bob.publicKey,
+            comment: comment_pkey,
+            commentAuthor: alice.publicKey,
+            tweet: legacy_pkey,
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidLegacyComment", "Expected 'InvalidLegacyComment' error for a missing legacy comment");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reaping should have failed without a legacy comment")
+    });
+  });
+
+  describe("Follow Graph", async () => {
+    it("Should follow another user and update both counters", async () => {
+      const [follow_pkey, follow_bump] = getFollowAddress(alice.publicKey, bob.publicKey, program.programId);
+
+      await program.methods.follow().accounts(
+        {
+          follower: alice.publicKey,
+          followerProfile: getProfileAddress(alice.publicKey, program.programId)[0],
+          followedProfile: getProfileAddress(bob.publicKey, program.programId)[0],
+          follow: follow_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const followData = await program.account.follow.fetch(follow_pkey);
+      assert.strictEqual(followData.follower.toString(), alice.publicKey.toString(), "Follower should be Alice");
+      assert.strictEqual(followData.followed.toString(), bob.publicKey.toString(), "Followed should be Bob");
+
+      const aliceProfile = await program.account.profile.fetch(getProfileAddress(alice.publicKey, program.programId)[0]);
+      const bobProfile = await program.account.profile.fetch(getProfileAddress(bob.publicKey, program.programId)[0]);
+      assert.strictEqual(aliceProfile.followingCount.toNumber(), 1, "Alice should follow one user");
+      assert.strictEqual(bobProfile.followerCount.toNumber(), 1, "Bob should have one follower");
+    });
+
+    it("Should fail when following the same user twice", async () => {
+      const [follow_pkey, follow_bump] = getFollowAddress(alice.publicKey, bob.publicKey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.follow().accounts(
+          {
+            follower: alice.publicKey,
+            followerProfile: getProfileAddress(alice.publicKey, program.programId)[0],
+            followedProfile: getProfileAddress(bob.publicKey, program.programId)[0],
+            follow: follow_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error when following twice")
+      }
+      assert.strictEqual(should_fail, "Failed", "Following the same user twice should have failed")
+    });
+
+    it("Should fail when following yourself", async () => {
+      const [follow_pkey, follow_bump] = getFollowAddress(bob.publicKey, bob.publicKey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.follow().accounts(
+          {
+            follower: bob.publicKey,
+            followerProfile: getProfileAddress(bob.publicKey, program.programId)[0],
+            followedProfile: getProfileAddress(bob.publicKey, program.programId)[0],
+            follow: follow_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "CannotFollowSelf", "Expected 'CannotFollowSelf' error when following yourself");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Following yourself should have failed")
+    });
+
+    it("Should unfollow, close the follow account and update both counters", async () => {
+      const [follow_pkey, follow_bump] = getFollowAddress(alice.publicKey, bob.publicKey, program.programId);
+
+      await program.methods.unfollow().accounts(
+        {
+          follower: alice.publicKey,
+          followerProfile: getProfileAddress(alice.publicKey, program.programId)[0],
+          followedProfile: getProfileAddress(bob.publicKey, program.programId)[0],
+          follow: follow_pkey,
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      assert.isNull(await provider.connection.getAccountInfo(follow_pkey), "Follow account should be closed");
+      const aliceProfile = await program.account.profile.fetch(getProfileAddress(alice.publicKey, program.programId)[0]);
+      const bobProfile = await program.account.profile.fetch(getProfileAddress(bob.publicKey, program.programId)[0]);
+      assert.strictEqual(aliceProfile.followingCount.toNumber(), 0, "Alice should follow nobody");
+      assert.strictEqual(bobProfile.followerCount.toNumber(), 0, "Bob should have no followers");
+    });
+  });
+
+  describe("Moderation", async () => {
+    const admin = (provider.wallet as anchor.Wallet).payer;
+    const dave = anchor.web3.Keypair.generate();
+    const [moderation_pkey, moderation_bump] = getModerationAddress(program.programId);
+    const [dave_ban_pkey, dave_ban_bump] = getBanAddress(dave.publicKey, program.programId);
+
+    it("Should let the tweet author hide and unhide a comment", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+
+      for (const hidden of [true, false]) {
+        await program.methods.hideComment(hidden).accounts(
+          {
+            tweetAuthor: bob.publicKey,
+            tweet: tweet_pkey,
+            comment: alice_unicode_comment_pkey,
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+
+        const commentData = await program.account.comment.fetch(alice_unicode_comment_pkey);
+        assert.strictEqual(commentData.hidden, hidden, `Comment hidden flag should be ${hidden}`);
+      }
+    });
+
+    it("Should fail when someone other than the tweet author hides a comment", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.hideComment(true).accounts(
+          {
+            tweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+            comment: alice_unicode_comment_pkey,
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when hiding a comment on someone else's tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the tweet author should be able to hide comments")
+    });
+
+    it("Should fail to hide a comment through a different tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.hideComment(true).accounts(
+          {
+            tweetAuthor: bob.publicKey,
+            tweet: tweet_pkey,
+            comment: alice_unicode_comment_pkey,
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "CommentTweetMismatch", "Expected 'CommentTweetMismatch' error for a comment on another tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Hiding a comment through another tweet should have failed")
+    });
+
+    it("Should fail to initialize moderation without the upgrade authority", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initializeModeration(bob.publicKey).accounts(
+          {
+            authority: bob.publicKey,
+            moderationConfig: moderation_pkey,
+            program: program.programId,
+            programData: getProgramDataAddress(program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when a non upgrade authority sets up moderation");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the upgrade authority should be able to set up moderation")
+    });
+
+    it("Should initialize moderation with the upgrade authority", async () => {
+      await program.methods.initializeModeration(admin.publicKey).accounts(
+        {
+          authority: admin.publicKey,
+          moderationConfig: moderation_pkey,
+          program: program.programId,
+          programData: getProgramDataAddress(program.programId)[0],
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).rpc({ commitment: "confirmed" })
+
+      const configData = await program.account.moderationConfig.fetch(moderation_pkey);
+      assert.strictEqual(configData.admin.toString(), admin.publicKey.toString(), "Moderation admin should be set");
+      assert.strictEqual(configData.maxCommentDepth, 8, "Max comment depth should start at the default");
+    });
+
+    it("Should let the admin configure the max comment depth", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.setCommentDepth(0).accounts(
+          {
+            admin: bob.publicKey,
+            moderationConfig: moderation_pkey,
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when someone other than the admin sets the depth");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the admin should be able to set the max comment depth")
+
+      await program.methods.setCommentDepth(0).accounts(
+        {
+          admin: admin.publicKey,
+          moderationConfig: moderation_pkey,
+        }
+      ).rpc({ commitment: "confirmed" })
+
+      should_fail = "This Should Fail"
+      try {
+        const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+        await program.methods.commentTweet("No replies allowed").accounts(
+          {
+            commentAuthor: alice.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: alice_unicode_comment_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: moderation_pkey,
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MaxCommentDepthReached", "Expected 'MaxCommentDepthReached' error with replies disabled");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Replies should follow the configured max depth")
+
+      await program.methods.setCommentDepth(8).accounts(
+        {
+          admin: admin.publicKey,
+          moderationConfig: moderation_pkey,
+        }
+      ).rpc({ commitment: "confirmed" })
+    });
+
+    it("Should fail when someone other than the admin bans a user", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.banUser().accounts(
+          {
+            admin: bob.publicKey,
+            moderationConfig: moderation_pkey,
+            user: dave.publicKey,
+            ban: dave_ban_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when a non-admin bans a user");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the admin should be able to ban users")
+    });
+
+    it("Should reject tweets, comments and reactions from a banned user", async () => {
+      await airdrop(provider.connection, dave.publicKey);
+      const [dave_profile_pkey, dave_profile_bump] = getProfileAddress(dave.publicKey, program.programId);
+      await program.methods.createProfile("dave", "Dave", "").accounts(
+        {
+          authority: dave.publicKey,
+          profile: dave_profile_pkey,
+          handleRegistry: getHandleAddress("dave", program.programId)[0],
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([dave]).rpc({ commitment: "confirmed" })
+
+      await program.methods.banUser().accounts(
+        {
+          admin: admin.publicKey,
+          moderationConfig: moderation_pkey,
+          user: dave.publicKey,
+          ban: dave_ban_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).rpc({ commitment: "confirmed" })
+
+      const banData = await program.account.ban.fetch(dave_ban_pkey);
+      assert.strictEqual(banData.user.toString(), dave.publicKey.toString(), "Ban should record the user");
+      assert.strictEqual(banData.bannedBy.toString(), admin.publicKey.toString(), "Ban should record the admin");
+
+      const [bob_tweet_pkey, bob_tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [dave_tweet_pkey, dave_tweet_bump] = getTweetAddress(dave.publicKey, 0, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, bob_tweet_pkey);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(dave.publicKey, bob_tweet_pkey, program.programId);
+
+      const attempts = [
+        program.methods.initialize("Banned", "Can I still post?", PLAIN_TWEET).accounts(
+          {
+            tweetAuthority: dave.publicKey,
+            profile: dave_profile_pkey,
+            tweet: dave_tweet_pkey,
+            ban: dave_ban_pkey,
+            gateMint: null,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ),
+        program.methods.commentTweet("Can I still comment?").accounts(
+          {
+            commentAuthor: dave.publicKey,
+            comment: comment_pkey,
+            tweet: bob_tweet_pkey,
+            ban: dave_ban_pkey,
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
+            parentComment: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ),
+        program.methods.likeTweet().accounts(
+          {
+            reactionAuthor: dave.publicKey,
+            tweetReaction: reaction_pkey,
+            tweet: bob_tweet_pkey,
+            ban: dave_ban_pkey,
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ),
+      ];
+      for (const attempt of attempts) {
+        let should_fail = "This Should Fail"
+        try {
+          await attempt.signers([dave]).rpc({ commitment: "confirmed" })
+        } catch (error) {
+          const err = anchor.AnchorError.parse(error.logs);
+          assert.strictEqual(err.error.errorCode.code, "UserBanned", "Expected 'UserBanned' error for a banned user");
+          should_fail = "Failed"
+        }
+        assert.strictEqual(should_fail, "Failed", "Banned users should not be able to post, comment or react")
+      }
+    });
+
+    it("Should let the admin lift a ban", async () => {
+      await program.methods.unbanUser().accounts(
+        {
+          admin: admin.publicKey,
+          moderationConfig: moderation_pkey,
+          ban: dave_ban_pkey,
+        }
+      ).rpc({ commitment: "confirmed" })
+
+      assert.isNull(await provider.connection.getAccountInfo(dave_ban_pkey), "Ban account should be closed");
+
+      const [dave_tweet_pkey, dave_tweet_bump] = getTweetAddress(dave.publicKey, 0, program.programId);
+      await program.methods.initialize("Unbanned", "Back again", PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: dave.publicKey,
+          profile: getProfileAddress(dave.publicKey, program.programId)[0],
+          tweet: dave_tweet_pkey,
+          ban: dave_ban_pkey,
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([dave]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(program, dave_tweet_pkey, dave.publicKey, "Unbanned", "Back again", 0, 0, dave_tweet_bump)
+    });
+  });
+
+  describe("Tipping", async () => {
+    const admin = (provider.wallet as anchor.Wallet).payer;
+    const [treasury_pkey, treasury_bump] = getTreasuryAddress(program.programId);
+    const FEE_BPS = 250;
+    const TIP = 1_000_000;
+
+    it("Should tip without a protocol fee before the treasury exists", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const authorBefore = await provider.connection.getBalance(bob.publicKey);
+
+      const signature = await program.methods.tipTweet(new anchor.BN(TIP)).accounts(
+        {
+          tipper: alice.publicKey,
+          tweet: tweet_pkey,
+          tweetAuthor: bob.publicKey,
+          treasury: treasury_pkey,
+          mint: null,
+          tipperTokenAccount: null,
+          authorTokenAccount: null,
+          treasuryTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      assert.strictEqual(await provider.connection.getBalance(bob.publicKey), authorBefore + TIP, "Author should receive the whole tip");
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.totalTips.toString(), "1", "Tweet should count the tip");
+
+      const events = await getEvents(program, signature);
+      assert.strictEqual(events[0].data.fee.toString(), "0", "Tips before the treasury exists should carry no fee");
+    });
+
+    it("Should fail to initialize the treasury without the upgrade authority", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initializeTreasury(FEE_BPS).accounts(
+          {
+            authority: bob.publicKey,
+            treasury: treasury_pkey,
+            program: program.programId,
+            programData: getProgramDataAddress(program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when a non upgrade authority sets up the treasury");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the upgrade authority should be able to set up the treasury")
+    });
+
+    it("Should fail to initialize the treasury with a fee above 100%", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initializeTreasury(10_001).accounts(
+          {
+            authority: admin.publicKey,
+            treasury: treasury_pkey,
+            program: program.programId,
+            programData: getProgramDataAddress(program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidFee", "Expected 'InvalidFee' error for a fee above 10000 bps");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "A fee above 10000 bps should have been rejected")
+    });
+
+    it("Should initialize the treasury with the upgrade authority", async () => {
+      await program.methods.initializeTreasury(FEE_BPS).accounts(
+        {
+          authority: admin.publicKey,
+          treasury: treasury_pkey,
+          program: program.programId,
+          programData: getProgramDataAddress(program.programId)[0],
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).rpc({ commitment: "confirmed" })
+
+      const treasuryData = await program.account.treasury.fetch(treasury_pkey);
+      assert.strictEqual(treasuryData.authority.toString(), admin.publicKey.toString(), "Treasury authority should be set");
+      assert.strictEqual(treasuryData.feeBps, FEE_BPS, "Treasury fee should be set");
+      assert.strictEqual(treasuryData.bump, treasury_bump, "Treasury bump should be set");
+    });
+
+    it("Should tip a tweet author in SOL and take the protocol fee", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const fee = TIP * FEE_BPS / 10_000;
+
+      const authorBefore = await provider.connection.getBalance(bob.publicKey);
+      const treasuryBefore = await provider.connection.getBalance(treasury_pkey);
+
+      const signature = await program.methods.tipTweet(new anchor.BN(TIP)).accounts(
+        {
+          tipper: alice.publicKey,
+          tweet: tweet_pkey,
+          tweetAuthor: bob.publicKey,
+          treasury: treasury_pkey,
+          mint: null,
+          tipperTokenAccount: null,
+          authorTokenAccount: null,
+          treasuryTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      assert.strictEqual(await provider.connection.getBalance(bob.publicKey), authorBefore + TIP - fee, "Author should receive the tip minus the fee");
+      assert.strictEqual(await provider.connection.getBalance(treasury_pkey), treasuryBefore + fee, "Treasury should receive the fee");
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.totalTips.toString(), "2", "Tweet should count both tips");
+
+      const events = await getEvents(program, signature);
+      assert.strictEqual(events.length, 1, "Tip should emit one event");
+      assert.strictEqual(events[0].name, "tipEvent", "Tip should emit a TipEvent");
+      assert.strictEqual(events[0].data.tipper.toString(), alice.publicKey.toString(), "Event should carry the tipper");
+      assert.strictEqual(events[0].data.tweetAuthor.toString(), bob.publicKey.toString(), "Event should carry the tweet author");
+      assert.isNull(events[0].data.mint, "SOL tips should have no mint");
+      assert.strictEqual(events[0].data.amount.toString(), TIP.toString(), "Event should carry the amount");
+      assert.strictEqual(events[0].data.fee.toString(), fee.toString(), "Event should carry the fee");
+    });
+
+    it("Should fail to skip the protocol fee by leaving out the treasury", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.tipTweet(new anchor.BN(TIP)).accounts(
+          {
+            tipper: alice.publicKey,
+            tweet: tweet_pkey,
+            tweetAuthor: bob.publicKey,
+            treasury: alice.publicKey,
+            mint: null,
+            tipperTokenAccount: null,
+            authorTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ConstraintSeeds", "Expected 'ConstraintSeeds' error for an account other than the treasury");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Tips should always go through the treasury")
+    });
+
+    it("Should fail to tip nothing", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.tipTweet(new anchor.BN(0)).accounts(
+          {
+            tipper: alice.publicKey,
+            tweet: tweet_pkey,
+            tweetAuthor: bob.publicKey,
+            treasury: treasury_pkey,
+            mint: null,
+            tipperTokenAccount: null,
+            authorTokenAccount: null,
+            treasuryTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidTipAmount", "Expected 'InvalidTipAmount' error for a zero tip");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "A zero tip should have been rejected")
+    });
+
+    it("Should fail when someone other than the treasury authority withdraws", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.withdrawTreasury(new anchor.BN(1)).accounts(
+          {
+            authority: alice.publicKey,
+            treasury: treasury_pkey,
+            mint: null,
+            treasuryTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: null,
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error when someone else withdraws fees");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the treasury authority should be able to withdraw")
+    });
+
+    it("Should let the treasury authority withdraw collected fees", async () => {
+      const fee = TIP * FEE_BPS / 10_000;
+      const treasuryBefore = await provider.connection.getBalance(treasury_pkey);
+
+      await program.methods.withdrawTreasury(new anchor.BN(fee)).accounts(
+        {
+          authority: admin.publicKey,
+          treasury: treasury_pkey,
+          mint: null,
+          treasuryTokenAccount: null,
+          destinationTokenAccount: null,
+          tokenProgram: null,
+        }
+      ).rpc({ commitment: "confirmed" })
+
+      assert.strictEqual(await provider.connection.getBalance(treasury_pkey), treasuryBefore - fee, "Treasury should pay out the fee");
+    });
+
+    it("Should fail to withdraw below the treasury's rent exempt minimum", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.withdrawTreasury(new anchor.BN(1)).accounts(
+          {
+            authority: admin.publicKey,
+            treasury: treasury_pkey,
+            mint: null,
+            treasuryTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: null,
+          }
+        ).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InsufficientTreasuryBalance", "Expected 'InsufficientTreasuryBalance' error when draining the treasury");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "The treasury should stay rent exempt")
+    });
+  });
+
+  describe("Retweets", async () => {
+    const quote = "Bob is right about this one";
+
+    it("Should retweet a tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.retweet().accounts(
+        {
+          retweetAuthor: alice.publicKey,
+          retweet: retweet_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const retweetData = await program.account.retweet.fetch(retweet_pkey);
+      assert.strictEqual(retweetData.retweetAuthor.toString(), alice.publicKey.toString(), "Retweet author should be set");
+      assert.strictEqual(retweetData.originalTweet.toString(), tweet_pkey.toString(), "Retweet should point at the original tweet");
+      assert.isNull(retweetData.quote, "Plain retweets should have no quote");
+      assert.strictEqual(retweetData.bump, retweet_bump, "Retweet bump should be set");
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.retweetCount.toString(), "1", "Tweet should count the retweet");
+    });
+
+    it("Should quote tweet a tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.quoteTweet(quote).accounts(
+        {
+          retweetAuthor: charlie.publicKey,
+          retweet: retweet_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const retweetData = await program.account.retweet.fetch(retweet_pkey);
+      assert.strictEqual(retweetData.quote, quote, "Quote tweets should keep their content");
+      assert.strictEqual(retweetData.originalTweet.toString(), tweet_pkey.toString(), "Quote tweet should point at the original tweet");
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.retweetCount.toString(), "2", "Tweet should count the quote tweet");
+    });
+
+    it("Should fail to retweet the same tweet twice", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.retweet().accounts(
+          {
+            retweetAuthor: charlie.publicKey,
+            retweet: retweet_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(charlie.publicKey, program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "A user should only retweet a tweet once")
+    });
+
+    it("Should fail to quote tweet with content too long", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET4, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.quoteTweet(content_bob3).accounts(
+          {
+            retweetAuthor: alice.publicKey,
+            retweet: retweet_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ContentTooLong", "Expected 'ContentTooLong' error for a quote longer than 500 characters");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Quote tweet with content too long should have failed")
+    });
+
+    it("Should fail when someone else undoes a retweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.undoRetweet().accounts(
+          {
+            retweetAuthor: charlie.publicKey,
+            retweet: retweet_pkey,
+            tweet: tweet_pkey,
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the retweet author should be able to undo it")
+    });
+
+    it("Should undo a retweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(bob.publicKey, BOB_TWEET1, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.undoRetweet().accounts(
+        {
+          retweetAuthor: alice.publicKey,
+          retweet: retweet_pkey,
+          tweet: tweet_pkey,
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      assert.isNull(await provider.connection.getAccountInfo(retweet_pkey), "Retweet account should be closed");
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.retweetCount.toString(), "1", "Tweet should no longer count the retweet");
+    });
+  });
+
+  describe("Events", async () => {
+    const topic_events = "Event driven";
+    const content_events = "Indexers should not need to scan accounts";
+    const comment_events = "Now they can follow the logs";
+
+    it("Should emit an event when a tweet is created", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_EVENTS, program.programId);
+
+      const signature = await program.methods.initialize(topic_events, content_events, PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: alice.publicKey,
+          profile: getProfileAddress(alice.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const events = await getEvents(program, signature);
+      assert.strictEqual(events.length, 1, "Tweet creation should emit one event");
+      assert.strictEqual(events[0].name, "initializeTweetEvent", "Tweet creation should emit an InitializeTweetEvent");
+      assert.strictEqual(events[0].data.tweet.toString(), tweet_pkey.toString(), "Event should carry the tweet");
+      assert.strictEqual(events[0].data.tweetAuthor.toString(), alice.publicKey.toString(), "Event should carry the author");
+      assert.strictEqual(events[0].data.index.toString(), ALICE_TWEET_EVENTS.toString(), "Event should carry the tweet index");
+      assert.strictEqual(events[0].data.topic, topic_events, "Event should carry the topic");
+      assert.deepEqual(Buffer.from(events[0].data.contentHash), sha256(content_events), "Event should carry the content hash");
+    });
+
+    it("Should emit events when a reaction is added and removed", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_EVENTS, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
+
+      const added = await program.methods.reactTweet({ love: {} }).accounts(
+        {
+          reactionAuthor: bob.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const removed = await program.methods.reactionRemove().accounts(
+        {
+          reactionAuthor: bob.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      for (const [signature, name] of [[added, "addReactionEvent"], [removed, "removeReactionEvent"]]) {
+        const events = await getEvents(program, signature);
+        assert.strictEqual(events.length, 1, `${name} should be the only event`);
+        assert.strictEqual(events[0].name, name, `Expected ${name}`);
+        assert.strictEqual(events[0].data.reactionAuthor.toString(), bob.publicKey.toString(), "Event should carry the reaction author");
+        assert.strictEqual(events[0].data.tweet.toString(), tweet_pkey.toString(), "Event should carry the tweet");
+        assert.deepEqual(events[0].data.reaction, { love: {} }, "Event should carry the reaction type");
+      }
+    });
+
+    it("Should emit events when a comment is added and removed", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_EVENTS, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+
+      const added = await program.methods.commentTweet(comment_events).accounts(
+        {
+          commentAuthor: bob.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      let events = await getEvents(program, added);
+      assert.strictEqual(events.length, 1, "Commenting should emit one event");
+      assert.strictEqual(events[0].name, "addCommentEvent", "Commenting should emit an AddCommentEvent");
+      assert.strictEqual(events[0].data.comment.toString(), comment_pkey.toString(), "Event should carry the comment");
+      assert.strictEqual(events[0].data.commentAuthor.toString(), bob.publicKey.toString(), "Event should carry the comment author");
+      assert.strictEqual(events[0].data.tweet.toString(), tweet_pkey.toString(), "Event should carry the tweet");
+      assert.strictEqual(events[0].data.id.toString(), "0", "Event should carry the comment id");
+      assert.isNull(events[0].data.parentComment, "Top-level comments have no parent comment");
+      assert.deepEqual(Buffer.from(events[0].data.contentHash), sha256(comment_events), "Event should carry the content hash");
+
+      const removed = await program.methods.commentRemove().accounts(
+        {
+          commentAuthor: bob.publicKey,
+          comment: comment_pkey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      events = await getEvents(program, removed);
+      assert.strictEqual(events.length, 1, "Removing a comment should emit one event");
+      assert.strictEqual(events[0].name, "removeCommentEvent", "Removing a comment should emit a RemoveCommentEvent");
+      assert.strictEqual(events[0].data.comment.toString(), comment_pkey.toString(), "Event should carry the comment");
+      assert.strictEqual(events[0].data.id.toString(), "0", "Event should carry the comment id");
+    });
+  });
+
+  describe("Unicode Validation", async () => {
+    // Within the character limits, but over them in bytes. Content stays
+    // short enough to fit in a single transaction.
+    const accented_topic = "é".repeat(32);
+    const emoji_content = "🚀".repeat(150);
+
+    it("Should count topic and content limits in characters, not bytes", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_UNICODE, program.programId);
+
+      await program.methods.initialize(accented_topic, emoji_content, PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(
+        program, tweet_pkey, charlie.publicKey, accented_topic, emoji_content, 0, 0, tweet_bump
+      )
+    });
+
+    it("Should fail when a unicode topic or content is too long in characters or bytes", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_UNICODE + 1, program.programId);
+
+      const attempts = [
+        { topic: accented_topic + "é", content: content_bob1, code: "TopicTooLong" },
+        { topic: topic_bob1, content: "B".repeat(500) + "🚀", code: "ContentTooLong" },
+        // 17 characters, but 68 bytes is over TOPIC_MAX_BYTES
+        { topic: "🚀".repeat(17), content: content_bob1, code: "TopicTooLong" },
+      ];
+      for (const attempt of attempts) {
+        let should_fail = "This Should Fail"
+        try {
+          await program.methods.initialize(attempt.topic, attempt.content, PLAIN_TWEET).accounts(
+            {
+              tweetAuthority: charlie.publicKey,
+              profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+              tweet: tweet_pkey,
+              ban: getBanAddress(charlie.publicKey, program.programId)[0],
+              gateMint: null,
+              poll: null,
+              systemProgram: anchor.web3.SystemProgram.programId
+            }
+          ).signers([charlie]).rpc({ commitment: "confirmed" })
+        } catch (error) {
+          const err = anchor.AnchorError.parse(error.logs);
+          assert.strictEqual(err.error.errorCode.code, attempt.code, `Expected '${attempt.code}' error`);
+          should_fail = "Failed"
+        }
+        assert.strictEqual(should_fail, "Failed", "Initialization over the character or byte limit should have failed")
+      }
+    });
+
+    it("Should store topics NFC normalized", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_UNICODE + 1, program.programId);
+      const decomposed_topic = "Cafe\u0301";
+
+      await program.methods.initialize(decomposed_topic, "Same topic, however it was typed", PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.topic, decomposed_topic.normalize("NFC"), "Topic should be stored in NFC form");
+      assert.strictEqual(tweetData.topic, "Caf\u00e9", "Topic should use the composed character");
+    });
+
+    it("Should count comment limits in characters, not bytes", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_UNICODE, program.programId);
+      const [comment_pkey, comment_bump] = await getNextCommentAddress(program, tweet_pkey);
+      const emoji_comment = "💯".repeat(150);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.commentTweet("C".repeat(500) + "💯").accounts(
+          {
+            commentAuthor: alice.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: null,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "CommentTooLong", "Expected 'CommentTooLong' error for 501 characters");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "A comment over 500 characters should have failed")
+
+      await program.methods.commentTweet(emoji_comment).accounts(
+        {
+          commentAuthor: alice.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      await checkComment(
+        program, comment_pkey, alice.publicKey, tweet_pkey, emoji_comment, comment_bump
+      )
+    });
+  });
+
+  describe("Reaping", async () => {
+    let comment_pkey: PublicKey;
+
+    it("Should fail to reap a reaction, comment or retweet while the tweet exists", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_REAP, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.initialize("Short lived", "This tweet will be gone soon", PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: alice.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      [comment_pkey] = await getNextCommentAddress(program, tweet_pkey);
+      await program.methods.commentTweet("Enjoy it while it lasts").accounts(
+        {
+          commentAuthor: alice.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      await program.methods.quoteTweet("Screenshot before it's gone").accounts(
+        {
+          retweetAuthor: alice.publicKey,
+          retweet: retweet_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const attempts = [
+        program.methods.reapReaction().accounts(
+          {
+            reaper: bob.publicKey,
+            tweetReaction: reaction_pkey,
+            reactionAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ),
+        program.methods.reapComment().accounts(
+          {
+            reaper: bob.publicKey,
+            comment: comment_pkey,
+            commentAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ),
+        program.methods.reapRetweet().accounts(
+          {
+            reaper: bob.publicKey,
+            retweet: retweet_pkey,
+            retweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ),
+      ];
+      for (const attempt of attempts) {
+        let should_fail = "This Should Fail"
+        try {
+          await attempt.signers([bob]).rpc({ commitment: "confirmed" })
+        } catch (error) {
+          const err = anchor.AnchorError.parse(error.logs);
+          assert.strictEqual(err.error.errorCode.code, "TweetStillExists", "Expected 'TweetStillExists' error while the tweet exists");
+          should_fail = "Failed"
+        }
+        assert.strictEqual(should_fail, "Failed", "Reaping should fail while the tweet exists")
+      }
+    });
+
+    it("Should fail to send the reaped rent to someone other than the author", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_REAP, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      await program.methods.deleteTweet().accounts(
+        {
+          tweetAuthor: charlie.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.reapReaction().accounts(
+          {
+            reaper: bob.publicKey,
+            tweetReaction: reaction_pkey,
+            reactionAuthor: bob.publicKey,
+            tweet: tweet_pkey,
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ConstraintAddress", "Expected 'ConstraintAddress' error for the wrong rent receiver");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reaped rent should only go to the author")
+    });
+
+    it("Should let anyone reap a reaction, comment and retweet of a deleted tweet", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_REAP, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
+      const [retweet_pkey, retweet_bump] = getRetweetAddress(alice.publicKey, tweet_pkey, program.programId);
+
+      const reaction_lamports = await provider.connection.getBalance(reaction_pkey);
+      const comment_lamports = await provider.connection.getBalance(comment_pkey);
+      const retweet_lamports = await provider.connection.getBalance(retweet_pkey);
+      const author_before = await provider.connection.getBalance(alice.publicKey);
+
+      await program.methods.reapReaction().accounts(
+        {
+          reaper: bob.publicKey,
+          tweetReaction: reaction_pkey,
+          reactionAuthor: alice.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      await program.methods.reapComment().accounts(
+        {
+          reaper: bob.publicKey,
+          comment: comment_pkey,
+          commentAuthor: alice.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      await program.methods.reapRetweet().accounts(
+        {
+          reaper: bob.publicKey,
+          retweet: retweet_pkey,
+          retweetAuthor: alice.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      assert.isNull(await provider.connection.getAccountInfo(reaction_pkey), "Reaction account should be closed");
+      assert.isNull(await provider.connection.getAccountInfo(comment_pkey), "Comment account should be closed");
+      assert.isNull(await provider.connection.getAccountInfo(retweet_pkey), "Retweet account should be closed");
+
+      const author_after = await provider.connection.getBalance(alice.publicKey);
+      assert.strictEqual(author_after, author_before + reaction_lamports + comment_lamports + retweet_lamports, "Rent should go back to the author");
+    });
+  });
+
+  describe("Token Gating", async () => {
+    const topic_gated = "Members only";
+    const content_gated = "Full post is at ipfs://gated-post, decrypt it with your membership key";
+    const full_content_gated = "The full members only post";
+    const min_balance = 100;
+
+    const mint = anchor.web3.Keypair.generate();
+    const bob_token_account = anchor.web3.Keypair.generate();
+
+    it("Should fail to create a gated tweet without a minimum balance", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_GATED, program.programId);
+      await createMint(provider, mint, alice.publicKey);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initialize(topic_gated, content_gated, { gate: { contentHash: Array.from(sha256(full_content_gated)), minBalance: new anchor.BN(0) }, poll: null, schedule: null }).accounts(
+          {
+            tweetAuthority: alice.publicKey,
+            profile: getProfileAddress(alice.publicKey, program.programId)[0],
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateMint: mint.publicKey,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidGateBalance", "Expected 'InvalidGateBalance' error for a zero minimum balance");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Gated tweets should need a minimum balance")
+    });
+
+    it("Should fail to create a gated tweet without the gate mint", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_GATED, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initialize(topic_gated, content_gated, { gate: { contentHash: Array.from(sha256(full_content_gated)), minBalance: new anchor.BN(min_balance) }, poll: null, schedule: null }).accounts(
+          {
+            tweetAuthority: alice.publicKey,
+            profile: getProfileAddress(alice.publicKey, program.programId)[0],
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MissingGateMint", "Expected 'MissingGateMint' error without the gate mint");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Gated tweets should need the gate mint")
+    });
+
+    it("Should create a gated tweet storing only the hash of its full content", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_GATED, program.programId);
+
+      await program.methods.initialize(topic_gated, content_gated, { gate: { contentHash: Array.from(sha256(full_content_gated)), minBalance: new anchor.BN(min_balance) }, poll: null, schedule: null }).accounts(
+        {
+          tweetAuthority: alice.publicKey,
+          profile: getProfileAddress(alice.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateMint: mint.publicKey,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.gateMint.toString(), mint.publicKey.toString(), "Tweet should store the gate mint");
+      assert.strictEqual(tweetData.minBalance.toNumber(), min_balance, "Tweet should store the minimum balance");
+      assert.deepEqual(Buffer.from(tweetData.contentHash), sha256(full_content_gated), "Tweet should store the hash of the off-chain content");
+      assert.strictEqual(tweetData.content, content_gated, "Tweet should store the public content");
+    });
+
+    it("Should fail to react to a gated tweet without a token account", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_GATED, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.likeTweet().accounts(
+          {
+            reactionAuthor: bob.publicKey,
+            tweetReaction: reaction_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TokenGateNotMet", "Expected 'TokenGateNotMet' error without a token account");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reacting to a gated tweet should need the gate token")
+    });
+
+    it("Should fail to comment on a gated tweet with a balance below the gate", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_GATED, program.programId);
+      const [comment_pkey] = await getNextCommentAddress(program, tweet_pkey);
+
+      await createTokenAccount(provider, bob_token_account, mint.publicKey, bob.publicKey);
+      await mintTo(provider, mint.publicKey, bob_token_account.publicKey, alice, min_balance - 1);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.commentTweet("Let me in").accounts(
+          {
+            commentAuthor: bob.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: null,
+            ban: getBanAddress(bob.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: bob_token_account.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TokenGateNotMet", "Expected 'TokenGateNotMet' error for a balance below the gate");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Commenting on a gated tweet should need the minimum balance")
+    });
+
+    it("Should react to and comment on a gated tweet once the balance meets the gate", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_GATED, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(bob.publicKey, tweet_pkey, program.programId);
+      const [comment_pkey] = await getNextCommentAddress(program, tweet_pkey);
+
+      await mintTo(provider, mint.publicKey, bob_token_account.publicKey, alice, 1);
+
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: bob.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          gateTokenAccount: bob_token_account.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      await program.methods.commentTweet("Glad to be in").accounts(
+        {
+          commentAuthor: bob.publicKey,
+          comment: comment_pkey,
+          tweet: tweet_pkey,
+          parentComment: null,
+          ban: getBanAddress(bob.publicKey, program.programId)[0],
+          moderationConfig: getModerationAddress(program.programId)[0],
+          gateTokenAccount: bob_token_account.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.reactionCounts[REACTION_INDEX.like].toNumber(), 1, "Like count should be one");
+      assert.strictEqual(tweetData.commentCount.toNumber(), 1, "Comment count should be one");
+    });
+  });
+
+  describe("Bookmarks", async () => {
+    it("Should create an empty bookmark list", async () => {
+      const [bookmark_list_pkey, bookmark_list_bump] = getBookmarkListAddress(bob.publicKey, program.programId);
+
+      await program.methods.createBookmarkList().accounts(
+        {
+          owner: bob.publicKey,
+          bookmarkList: bookmark_list_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const bookmarkListData = await program.account.bookmarkList.fetch(bookmark_list_pkey);
+      assert.strictEqual(bookmarkListData.owner.toString(), bob.publicKey.toString(), "Bookmark list should belong to Bob");
+      assert.strictEqual(bookmarkListData.tweets.length, 0, "Bookmark list should start empty");
+      assert.strictEqual(bookmarkListData.bump, bookmark_list_bump, "Bookmark list bump should be set");
+    });
+
+    it("Should bookmark tweets, growing the list at the owner's expense", async () => {
+      const [bookmark_list_pkey, bookmark_list_bump] = getBookmarkListAddress(bob.publicKey, program.programId);
+      const [charlie_tweet_pkey, charlie_tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
+      const [alice_tweet_pkey, alice_tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_SWITCH, program.programId);
+
+      const size_before = (await provider.connection.getAccountInfo(bookmark_list_pkey)).data.length;
+      const list_lamports_before = await provider.connection.getBalance(bookmark_list_pkey);
+
+      for (const tweet_pkey of [charlie_tweet_pkey, alice_tweet_pkey]) {
+        await program.methods.bookmarkTweet().accounts(
+          {
+            owner: bob.publicKey,
+            bookmarkList: bookmark_list_pkey,
+            tweet: tweet_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      }
+
+      const bookmarkListData = await program.account.bookmarkList.fetch(bookmark_list_pkey);
+      assert.deepEqual(bookmarkListData.tweets.map((tweet) => tweet.toString()), [charlie_tweet_pkey.toString(), alice_tweet_pkey.toString()], "Bookmarks should be kept in order");
+
+      const size_after = (await provider.connection.getAccountInfo(bookmark_list_pkey)).data.length;
+      assert.strictEqual(size_after, size_before + 2 * 32, "Bookmark list should grow by one entry per bookmark");
+      const list_lamports_after = await provider.connection.getBalance(bookmark_list_pkey);
+      assert.strictEqual(list_lamports_after, await provider.connection.getMinimumBalanceForRentExemption(size_after), "Bookmark list should stay rent exempt");
+      assert.isAbove(list_lamports_after, list_lamports_before, "Owner should pay for the extra space");
+    });
+
+    it("Should fail to bookmark the same tweet twice", async () => {
+      const [bookmark_list_pkey, bookmark_list_bump] = getBookmarkListAddress(bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.bookmarkTweet().accounts(
+          {
+            owner: bob.publicKey,
+            bookmarkList: bookmark_list_pkey,
+            tweet: tweet_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "AlreadyBookmarked", "Expected 'AlreadyBookmarked' error for a duplicate bookmark");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "A tweet should only be bookmarked once")
+    });
+
+    it("Should unbookmark a tweet, shrinking the list and refunding the owner", async () => {
+      const [bookmark_list_pkey, bookmark_list_bump] = getBookmarkListAddress(bob.publicKey, program.programId);
+      const [charlie_tweet_pkey, charlie_tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
+      const [alice_tweet_pkey, alice_tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_SWITCH, program.programId);
+
+      const size_before = (await provider.connection.getAccountInfo(bookmark_list_pkey)).data.length;
+      const list_lamports_before = await provider.connection.getBalance(bookmark_list_pkey);
+      const owner_before = await provider.connection.getBalance(bob.publicKey);
+
+      await program.methods.unbookmarkTweet(charlie_tweet_pkey).accounts(
+        {
+          owner: bob.publicKey,
+          bookmarkList: bookmark_list_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const bookmarkListData = await program.account.bookmarkList.fetch(bookmark_list_pkey);
+      assert.deepEqual(bookmarkListData.tweets.map((tweet) => tweet.toString()), [alice_tweet_pkey.toString()], "Only the other bookmark should be left");
+
+      const size_after = (await provider.connection.getAccountInfo(bookmark_list_pkey)).data.length;
+      assert.strictEqual(size_after, size_before - 32, "Bookmark list should shrink by one entry");
+      const list_lamports_after = await provider.connection.getBalance(bookmark_list_pkey);
+      const owner_after = await provider.connection.getBalance(bob.publicKey);
+      assert.strictEqual(owner_after, owner_before + list_lamports_before - list_lamports_after, "Freed rent should go back to the owner");
+    });
+
+    it("Should fail to unbookmark a tweet that is not bookmarked", async () => {
+      const [bookmark_list_pkey, bookmark_list_bump] = getBookmarkListAddress(bob.publicKey, program.programId);
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET1, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.unbookmarkTweet(tweet_pkey).accounts(
+          {
+            owner: bob.publicKey,
+            bookmarkList: bookmark_list_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotBookmarked", "Expected 'NotBookmarked' error for a missing bookmark");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only bookmarked tweets can be unbookmarked")
+    });
+  });
+
+  describe("Polls", async () => {
+    const topic_poll = "Weekend plans";
+    const content_poll = "Where should we meet up?";
+    const poll_options = ["Beach", "Mountains", "Stay home"];
+
+    async function createPollTweet(index: number, options: string[], ends_at: number, with_poll_account = true) {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, index, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+
+      await program.methods.initialize(topic_poll, content_poll, { gate: null, poll: { options, endsAt: new anchor.BN(ends_at) }, schedule: null }).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: with_poll_account ? poll_pkey : null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+    }
+
+    async function vote(voter: anchor.web3.Keypair, index: number, option: number) {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, index, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+      const [poll_vote_pkey, poll_vote_bump] = getPollVoteAddress(poll_pkey, voter.publicKey, program.programId);
+
+      await program.methods.votePoll(option).accounts(
+        {
+          voter: voter.publicKey,
+          poll: poll_pkey,
+          tweet: tweet_pkey,
+          pollVote: poll_vote_pkey,
+          ban: getBanAddress(voter.publicKey, program.programId)[0],
+          gateTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([voter]).rpc({ commitment: "confirmed" })
+    }
+
+    async function closePoll(closer: anchor.web3.Keypair, index: number) {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, index, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+
+      await program.methods.closePoll().accounts(
+        {
+          closer: closer.publicKey,
+          poll: poll_pkey,
+          author: charlie.publicKey,
+          tweet: tweet_pkey
+        }
+      ).signers([closer]).rpc({ commitment: "confirmed" })
+    }
+
+    async function reapPollVote(voter: anchor.web3.Keypair, index: number) {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, index, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+      const [poll_vote_pkey, poll_vote_bump] = getPollVoteAddress(poll_pkey, voter.publicKey, program.programId);
+
+      await program.methods.reapPollVote().accounts(
+        {
+          reaper: voter.publicKey,
+          pollVote: poll_vote_pkey,
+          voter: voter.publicKey,
+          poll: poll_pkey
+        }
+      ).signers([voter]).rpc({ commitment: "confirmed" })
+    }
+
+    async function expectError(action: Promise<any>, code: string, message: string) {
+      let should_fail = "This Should Fail"
+      try {
+        await action
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, code, `Expected '${code}' error`);
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", message)
+    }
+
+    it("Should fail to attach a poll with too few or too many options", async () => {
+      const ends_at = (await getClusterTime(provider.connection)) + 3600;
+      await expectError(createPollTweet(CHARLIE_TWEET_POLL, ["Only one"], ends_at), "InvalidPollOptionCount", "Polls should need at least two options");
+      await expectError(createPollTweet(CHARLIE_TWEET_POLL, ["A", "B", "C", "D", "E"], ends_at), "InvalidPollOptionCount", "Polls should have at most four options");
+    });
+
+    it("Should fail to attach a poll with an empty or too long option", async () => {
+      const ends_at = (await getClusterTime(provider.connection)) + 3600;
+      await expectError(createPollTweet(CHARLIE_TWEET_POLL, ["Beach", " "], ends_at), "PollOptionEmpty", "Poll options should not be blank");
+      await expectError(createPollTweet(CHARLIE_TWEET_POLL, ["Beach", "M".repeat(51)], ends_at), "PollOptionTooLong", "Poll options should be at most 50 characters");
+    });
+
+    it("Should fail to attach a poll that already ended or without its account", async () => {
+      const ends_at = (await getClusterTime(provider.connection)) - 1;
+      await expectError(createPollTweet(CHARLIE_TWEET_POLL, poll_options, ends_at), "InvalidPollEnd", "Polls should end in the future");
+      await expectError(createPollTweet(CHARLIE_TWEET_POLL, poll_options, ends_at + 3600, false), "PollAccountMismatch", "Poll options should need the poll account");
+    });
+
+    it("Should create a tweet with a poll", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_POLL, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+      const ends_at = (await getClusterTime(provider.connection)) + 3600;
+
+      await createPollTweet(CHARLIE_TWEET_POLL, poll_options, ends_at);
+
+      const pollData = await program.account.poll.fetch(poll_pkey);
+      assert.strictEqual(pollData.tweet.toString(), tweet_pkey.toString(), "Poll should belong to the tweet");
+      assert.strictEqual(pollData.author.toString(), charlie.publicKey.toString(), "Poll should remember who paid its rent");
+      assert.deepEqual(pollData.options, poll_options, "Poll should store its options");
+      assert.deepEqual(pollData.votes.map((votes) => votes.toNumber()), [0, 0, 0, 0], "Poll should start without votes");
+      assert.strictEqual(pollData.endsAt.toNumber(), ends_at, "Poll should store its end time");
+      assert.strictEqual(pollData.bump, poll_bump, "Poll bump should be set");
+    });
+
+    it("Should count one vote per user", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_POLL, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+      const [poll_vote_pkey, poll_vote_bump] = getPollVoteAddress(poll_pkey, alice.publicKey, program.programId);
+
+      await vote(alice, CHARLIE_TWEET_POLL, 1);
+      await vote(bob, CHARLIE_TWEET_POLL, 1);
+
+      const pollData = await program.account.poll.fetch(poll_pkey);
+      assert.deepEqual(pollData.votes.map((votes) => votes.toNumber()), [0, 2, 0, 0], "Both votes should be tallied on the second option");
+
+      const pollVoteData = await program.account.pollVote.fetch(poll_vote_pkey);
+      assert.strictEqual(pollVoteData.voter.toString(), alice.publicKey.toString(), "Vote should belong to Alice");
+      assert.strictEqual(pollVoteData.poll.toString(), poll_pkey.toString(), "Vote should belong to the poll");
+      assert.strictEqual(pollVoteData.option, 1, "Vote should store the option");
+
+      let should_fail = "This Should Fail"
+      try {
+        await vote(alice, CHARLIE_TWEET_POLL, 0);
+      } catch (error) {
+        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error for a second vote")
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Users should only vote once")
+    });
+
+    it("Should fail to vote for an option the poll does not have", async () => {
+      await expectError(vote(charlie, CHARLIE_TWEET_POLL, poll_options.length), "InvalidPollOption", "Votes should be for an existing option");
+    });
+
+    it("Should fail to vote after the poll ended", async () => {
+      const ends_at = (await getClusterTime(provider.connection)) + 2;
+      await createPollTweet(CHARLIE_TWEET_SHORT_POLL, poll_options, ends_at);
+
+      await waitUntil(provider.connection, ends_at);
+
+      await expectError(vote(alice, CHARLIE_TWEET_SHORT_POLL, 0), "PollEnded", "Votes after the end time should be rejected");
+    });
+
+    it("Should only let the author close a poll once it ended", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_POLL, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+
+      await expectError(closePoll(charlie, CHARLIE_TWEET_POLL), "PollNotEnded", "Open polls should not be closable");
+      await expectError(closePoll(alice, CHARLIE_TWEET_SHORT_POLL), "Unauthorized", "Only the author should close a poll of a live tweet");
+
+      const [short_tweet_pkey, short_tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_SHORT_POLL, program.programId);
+      const [short_poll_pkey, short_poll_bump] = getPollAddress(short_tweet_pkey, program.programId);
+      await closePoll(charlie, CHARLIE_TWEET_SHORT_POLL);
+
+      assert.isNull(await provider.connection.getAccountInfo(short_poll_pkey), "Ended poll should be closed");
+      assert.isNotNull(await provider.connection.getAccountInfo(poll_pkey), "Open poll should be kept");
+    });
+
+    it("Should not reap votes of a poll that is still open", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_POLL, program.programId);
+      const [poll_pkey, poll_bump] = getPollAddress(tweet_pkey, program.programId);
+      const [poll_vote_pkey, poll_vote_bump] = getPollVoteAddress(poll_pkey, alice.publicKey, program.programId);
+
+      await expectError(reapPollVote(alice, CHARLIE_TWEET_POLL), "PollNotEnded", "Votes on an open poll should be kept");
+      assert.isNotNull(await provider.connection.getAccountInfo(poll_vote_pkey), "Vote should still exist");
+    });
+  });
+
+  describe("Scheduled Tweets", async () => {
+    const topic_scheduled = "Announcement";
+    const content_scheduled = "Doors open at noon";
+
+    it("Should fail to schedule a tweet that expires before it is published", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_SCHEDULED, program.programId);
+      const publish_at = (await getClusterTime(provider.connection)) + 3600;
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initialize(topic_scheduled, content_scheduled, { gate: null, poll: null, schedule: { publishAt: new anchor.BN(publish_at), expiresAt: new anchor.BN(publish_at) } }).accounts(
+          {
+            tweetAuthority: charlie.publicKey,
+            profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+            tweet: tweet_pkey,
+            ban: getBanAddress(charlie.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidSchedule", "Expected 'InvalidSchedule' error for an expiry before publishing");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Tweets should expire after they are published")
+    });
+
+    it("Should reject reactions before a scheduled tweet is published", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_SCHEDULED, program.programId);
+      const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
+      const publish_at = (await getClusterTime(provider.connection)) + 3600;
+
+      await program.methods.initialize(topic_scheduled, content_scheduled, { gate: null, poll: null, schedule: { publishAt: new anchor.BN(publish_at), expiresAt: null } }).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.publishAt.toNumber(), publish_at, "Tweet should store its publish time");
+      assert.strictEqual(tweetData.expiresAt.toNumber(), 0, "Tweet without an expiry should store zero");
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.likeTweet().accounts(
+          {
+            reactionAuthor: alice.publicKey,
+            tweetReaction: reaction_pkey,
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TweetNotPublished", "Expected 'TweetNotPublished' error before the publish time");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reactions should wait for the publish time")
+    });
+
+    it("Should fail to expire a tweet that has no expiry", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_SCHEDULED, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.expireTweet().accounts(
+          {
+            closer: bob.publicKey,
+            tweet: tweet_pkey,
+            tweetAuthor: charlie.publicKey,
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TweetNotExpired", "Expected 'TweetNotExpired' error for a tweet without an expiry");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Tweets without an expiry should not be expired")
+    });
+
+    it("Should reject comments on an expired tweet and let anyone close it", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(charlie.publicKey, CHARLIE_TWEET_EXPIRING, program.programId);
+      const expires_at = (await getClusterTime(provider.connection)) + 2;
+
+      await program.methods.initialize(topic_scheduled, content_scheduled, { gate: null, poll: null, schedule: { publishAt: null, expiresAt: new anchor.BN(expires_at) } }).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          profile: getProfileAddress(charlie.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(charlie.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await waitUntil(provider.connection, expires_at);
+
+      const [comment_pkey] = await getNextCommentAddress(program, tweet_pkey);
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.commentTweet("Too late?").accounts(
+          {
+            commentAuthor: alice.publicKey,
+            comment: comment_pkey,
+            tweet: tweet_pkey,
+            parentComment: null,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            moderationConfig: getModerationAddress(program.programId)[0],
+            gateTokenAccount: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TweetExpired", "Expected 'TweetExpired' error after the expiry");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Comments should be rejected after the expiry")
+
+      const tweet_lamports = await provider.connection.getBalance(tweet_pkey);
+      const author_before = await provider.connection.getBalance(charlie.publicKey);
+
+      await program.methods.expireTweet().accounts(
+        {
+          closer: bob.publicKey,
+          tweet: tweet_pkey,
+          tweetAuthor: charlie.publicKey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      assert.isNull(await provider.connection.getAccountInfo(tweet_pkey), "Expired tweet should be closed");
+      const author_after = await provider.connection.getBalance(charlie.publicKey);
+      assert.strictEqual(author_after, author_before + tweet_lamports, "Rent should go back to the author");
+    });
+  });
+
+  describe("Hashtags and Mentions", async () => {
+    const topic_tags = "Tags";
+    const content_tags = "#Solana is fast, ask @bob #solana #rust_lang";
+
+    it("Should fail to tweet more than five hashtags and mentions", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initialize(topic_tags, "#a #b #c #d #e #f", PLAIN_TWEET).accounts(
+          {
+            tweetAuthority: alice.publicKey,
+            profile: getProfileAddress(alice.publicKey, program.programId)[0],
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TooManyTags", "Expected 'TooManyTags' error for six hashtags");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Tweets should be limited to five hashtags and mentions")
+    });
+
+    it("Should fail to tweet with missing tag accounts", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.initialize(topic_tags, content_tags, PLAIN_TWEET).accounts(
+          {
+            tweetAuthority: alice.publicKey,
+            profile: getProfileAddress(alice.publicKey, program.programId)[0],
+            tweet: tweet_pkey,
+            ban: getBanAddress(alice.publicKey, program.programId)[0],
+            gateMint: null,
+            poll: null,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).remainingAccounts([
+          { pubkey: getTagIndexAddress("solana", tweet_pkey, program.programId)[0], isSigner: false, isWritable: true },
+        ]).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TagAccountsMismatch", "Expected 'TagAccountsMismatch' error for missing tag accounts");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Every hashtag and mention needs its accounts")
+    });
+
+    it("Should index hashtags and notify mentioned users", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+      const bob_profile = getProfileAddress(bob.publicKey, program.programId)[0];
+
+      await program.methods.initialize(topic_tags, content_tags, PLAIN_TWEET).accounts(
+        {
+          tweetAuthority: alice.publicKey,
+          profile: getProfileAddress(alice.publicKey, program.programId)[0],
+          tweet: tweet_pkey,
+          ban: getBanAddress(alice.publicKey, program.programId)[0],
+          gateMint: null,
+          poll: null,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).remainingAccounts([
+        { pubkey: getTagIndexAddress("solana", tweet_pkey, program.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: getHandleAddress("bob", program.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: getMentionAddress(bob_profile, tweet_pkey, program.programId)[0], isSigner: false, isWritable: true },
+        { pubkey: getTagIndexAddress("rust_lang", tweet_pkey, program.programId)[0], isSigner: false, isWritable: true },
+      ]).signers([alice]).rpc({ commitment: "confirmed" })
+
+      for (const tag of ["solana", "rust_lang"]) {
+        const tagIndex = await program.account.tagIndex.fetch(getTagIndexAddress(tag, tweet_pkey, program.programId)[0]);
+        assert.deepEqual(Buffer.from(tagIndex.tagHash), sha256(tag), `Tag index should hold the hash of "${tag}"`);
+        assert.strictEqual(tagIndex.tweet.toString(), tweet_pkey.toString(), "Tag index should point at the tweet");
+        assert.strictEqual(tagIndex.tweetAuthor.toString(), alice.publicKey.toString(), "Tag index should record who paid for it");
+      }
+
+      const mention = await program.account.mention.fetch(getMentionAddress(bob_profile, tweet_pkey, program.programId)[0]);
+      assert.strictEqual(mention.profile.toString(), bob_profile.toString(), "Mention should notify bob's profile");
+      assert.strictEqual(mention.tweet.toString(), tweet_pkey.toString(), "Mention should point at the tweet");
+      assert.strictEqual(mention.tweetAuthor.toString(), alice.publicKey.toString(), "Mention should record who paid for it");
+    });
+
+    it("Should fail to close a mention of another user", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+      const [mention_pkey, mention_bump] = getMentionAddress(getProfileAddress(bob.publicKey, program.programId)[0], tweet_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.closeMention().accounts(
+          {
+            user: alice.publicKey,
+            profile: getProfileAddress(alice.publicKey, program.programId)[0],
+            mention: mention_pkey,
+            tweetAuthor: alice.publicKey,
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Unauthorized", "Expected 'Unauthorized' error for a mention of another user");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the mentioned user can close a mention")
+    });
+
+    it("Should let the mentioned user close the mention", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+      const [mention_pkey, mention_bump] = getMentionAddress(getProfileAddress(bob.publicKey, program.programId)[0], tweet_pkey, program.programId);
+
+      const mention_lamports = await provider.connection.getBalance(mention_pkey);
+      const author_before = await provider.connection.getBalance(alice.publicKey);
+
+      await program.methods.closeMention().accounts(
+        {
+          user: bob.publicKey,
+          profile: getProfileAddress(bob.publicKey, program.programId)[0],
+          mention: mention_pkey,
+          tweetAuthor: alice.publicKey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      assert.isNull(await provider.connection.getAccountInfo(mention_pkey), "Mention should be closed");
+      const author_after = await provider.connection.getBalance(alice.publicKey);
+      assert.strictEqual(author_after, author_before + mention_lamports, "Rent should go back to the tweet author");
+    });
+
+    it("Should not reap a tag index while its tweet exists", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.reapTagIndex().accounts(
+          {
+            reaper: charlie.publicKey,
+            tagIndex: getTagIndexAddress("solana", tweet_pkey, program.programId)[0],
+            tweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TweetStillExists", "Expected 'TweetStillExists' error while the tweet exists");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Tag indexes of a live tweet can't be reaped")
+    });
+
+    it("Should let anyone reap tag indexes once the tweet is deleted", async () => {
+      const [tweet_pkey, tweet_bump] = getTweetAddress(alice.publicKey, ALICE_TWEET_TAGS, program.programId);
+
+      await program.methods.deleteTweet().accounts(
+        {
+          tweetAuthor: alice.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      for (const tag of ["solana", "rust_lang"]) {
+        const [tag_index_pkey, tag_index_bump] = getTagIndexAddress(tag, tweet_pkey, program.programId);
+        const index_lamports = await provider.connection.getBalance(tag_index_pkey);
+        const author_before = await provider.connection.getBalance(alice.publicKey);
+
+        await program.methods.reapTagIndex().accounts(
+          {
+            reaper: charlie.publicKey,
+            tagIndex: tag_index_pkey,
+            tweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+        assert.isNull(await provider.connection.getAccountInfo(tag_index_pkey), `Tag index of "${tag}" should be closed`);
+        const author_after = await provider.connection.getBalance(alice.publicKey);
+        assert.strictEqual(author_after, author_before + index_lamports, "Rent should go back to the tweet author");
+      }
+    });
+  });
+
+});
+
+
+async function airdrop(connection: any, address: any, amount = 1000000000) {
+  await connection.confirmTransaction(await connection.requestAirdrop(address, amount), "confirmed");
+}
+
+// Unix timestamp of the latest confirmed block, the time the program sees in Clock
+async function getClusterTime(connection: anchor.web3.Connection) {
+  const slot = await connection.getSlot("confirmed");
+  return await connection.getBlockTime(slot);
+}
+
+async function waitUntil(connection: anchor.web3.Connection, timestamp: number) {
+  while ((await getClusterTime(connection)) <= timestamp) {
+    await new Promise((resolve) => setTimeout(resolve, 500));
+  }
+}
+
+async function getEvents(program: anchor.Program<Twitter>, signature: string) {
+  const tx = await program.provider.connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+  const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+  return [...parser.parseLogs(tx.meta.logMessages)];
+}
+
+function sha256(data: string) {
+  return crypto.createHash("sha256").update(data).digest();
+}
+
+// Minimal SPL Token instructions, so the gating tests don't need @solana/spl-token
+async function createMint(provider: anchor.AnchorProvider, mint: anchor.web3.Keypair, authority: PublicKey) {
+  const space = 82;
+  const data = Buffer.alloc(67);
+  data.writeUInt8(20, 0); // InitializeMint2
+  data.writeUInt8(0, 1); // decimals
+  authority.toBuffer().copy(data, 2);
+  data.writeUInt8(0, 34); // no freeze authority
+
+  const tx = new anchor.web3.Transaction().add(
+    anchor.web3.SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: mint.publicKey,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+      space,
+      programId: TOKEN_PROGRAM_ID,
+    }),
+    new anchor.web3.TransactionInstruction({
+      keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+      programId: TOKEN_PROGRAM_ID,
+      data,
+    })
+  );
+  await provider.sendAndConfirm(tx, [mint], { commitment: "confirmed" });
+}
+
+async function createTokenAccount(provider: anchor.AnchorProvider, account: anchor.web3.Keypair, mint: PublicKey, owner: PublicKey) {
+  const space = 165;
+  const data = Buffer.alloc(33);
+  data.writeUInt8(18, 0); // InitializeAccount3
+  owner.toBuffer().copy(data, 1);
+
+  const tx = new anchor.web3.Transaction().add(
+    anchor.web3.SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: account.publicKey,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+      space,
+      programId: TOKEN_PROGRAM_ID,
+    }),
+    new anchor.web3.TransactionInstruction({
+      keys: [
+        { pubkey: account.publicKey, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+      ],
+      programId: TOKEN_PROGRAM_ID,
+      data,
+    })
+  );
+  await provider.sendAndConfirm(tx, [account], { commitment: "confirmed" });
+}
+
+async function mintTo(provider: anchor.AnchorProvider, mint: PublicKey, account: PublicKey, authority: anchor.web3.Keypair, amount: number) {
+  const data = Buffer.alloc(9);
+  data.writeUInt8(7, 0); // MintTo
+  data.writeBigUInt64LE(BigInt(amount), 1);
+
+  const tx = new anchor.web3.Transaction().add(
+    new anchor.web3.TransactionInstruction({
+      keys: [
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: account, isSigner: false, isWritable: true },
+        { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      ],
+      programId: TOKEN_PROGRAM_ID,
+      data,
+    })
+  );
+  await provider.sendAndConfirm(tx, [authority], { commitment: "confirmed" });
+}
+
+function getCommentAddress(parent_tweet: PublicKey, id: number, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(COMMENT_SEED),
+      parent_tweet.toBuffer(),
+      new anchor.BN(id).toArrayLike(Buffer, "le", 8)
+    ], programID);
+}
+
+async function getNextCommentAddress(program: anchor.Program<Twitter>, parent_tweet: PublicKey) {
+  const tweetData = await program.account.tweet.fetch(parent_tweet);
+  return getCommentAddress(parent_tweet, tweetData.commentCount.toNumber(), program.programId);
+}
+
+function getProfileAddress(author: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(PROFILE_SEED),
+      author.toBuffer()
+    ], programID);
+}
+
+function getHandleAddress(handle: string, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(HANDLE_SEED),
+      anchor.utils.bytes.utf8.encode(handle)
+    ], programID);
+}
+
+function getFollowAddress(follower: PublicKey, followed: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(FOLLOW_SEED),
+      follower.toBuffer(),
+      followed.toBuffer()
+    ], programID);
+}
+
+function getModerationAddress(programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(MODERATION_SEED)
+    ], programID);
+}
+
+function getBanAddress(user: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(BAN_SEED),
+      user.toBuffer()
+    ], programID);
+}
+
+function getTreasuryAddress(programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TREASURY_SEED)
+    ], programID);
+}
+
+function getProgramDataAddress(programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [programID.toBuffer()], new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111"));
+}
+
+function getTweetAddress(author: PublicKey, index: number, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TWEET_SEED),
+      author.toBuffer(),
+      new anchor.BN(index).toArrayLike(Buffer, "le", 8)
+    ], programID);
+}
+
+function getLegacyTweetAddress(topic: string, author: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(topic),
+      anchor.utils.bytes.utf8.encode(TWEET_SEED),
+      author.toBuffer()
+    ], programID);
+}
+
+function getLegacyCommentAddress(author: PublicKey, content: string, tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(COMMENT_SEED),
+      author.toBuffer(),
+      sha256(content),
+      tweet.toBuffer()
+    ], programID);
+}
+
+function getRetweetAddress(author: PublicKey, tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(RETWEET_SEED),
+      author.toBuffer(),
+      tweet.toBuffer()
+    ], programID);
+}
+
+function getBookmarkListAddress(owner: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(BOOKMARK_SEED),
+      owner.toBuffer()
+    ], programID);
+}
+
+function getPollAddress(tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(POLL_SEED),
+      tweet.toBuffer()
+    ], programID);
+}
+
+function getPollVoteAddress(poll: PublicKey, voter: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(POLL_VOTE_SEED),
+      poll.toBuffer(),
+      voter.toBuffer()
+    ], programID);
+}
+
+function getTagIndexAddress(tag: string, tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TAG_SEED),
+      sha256(tag),
+      tweet.toBuffer()
+    ], programID);
+}
+
+function getMentionAddress(profile: PublicKey, tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(MENTION_SEED),
+      profile.toBuffer(),
+      tweet.toBuffer()
     ], programID);
 }
 
@@ -901,10 +3887,12 @@ async function checkTweet(
     assert.strictEqual(tweetData.content, content, `Tweet content should be "${content}" but was "${tweetData.content}"`);
   }
   if (likes || likes == 0) {
-    assert.strictEqual(tweetData.likes.toString(), new anchor.BN(likes).toString(), `Tweet likes should be ${likes} but was ${tweetData.likes.toString()}`)
+    const tweetLikes = tweetData.reactionCounts[REACTION_INDEX.like];
+    assert.strictEqual(tweetLikes.toString(), new anchor.BN(likes).toString(), `Tweet likes should be ${likes} but was ${tweetLikes.toString()}`)
   }
   if (dislikes || dislikes == 0) {
-    assert.strictEqual(tweetData.dislikes.toString(), new anchor.BN(dislikes).toString(), `Tweet dislikes should be ${dislikes} but was ${tweetData.dislikes.toString()}`)
+    const tweetDislikes = tweetData.reactionCounts[REACTION_INDEX.dislike];
+    assert.strictEqual(tweetDislikes.toString(), new anchor.BN(dislikes).toString(), `Tweet dislikes should be ${dislikes} but was ${tweetDislikes.toString()}`)
   }
   if (bump) {
     assert.strictEqual(tweetData.bump.toString(), bump.toString(), `Tweet bump should be ${bump} but was ${tweetData.bump}`)